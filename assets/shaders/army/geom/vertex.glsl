@@ -3,6 +3,8 @@
 uniform mat4 model;
 uniform mat4 view;
 uniform mat4 projection;
+// The inverse transpose of view * model.
+uniform mat3 normal_matrix;
 
 in vec3 position;
 in vec3 normal;
@@ -18,6 +20,6 @@ void main() {
     vec4 hpos = vec4(position, 1.0);
     gl_Position = projection * view_model * hpos;
     model_position = vec3(view_model * hpos);
-    model_normal = mat3(transpose(inverse(view_model))) * normal;
+    model_normal = normal_matrix * normal;
     model_texture = texture;
 }
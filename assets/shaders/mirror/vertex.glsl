@@ -3,6 +3,8 @@
 uniform mat4 model;
 uniform mat4 view;
 uniform mat4 projection;
+// The inverse transpose of model.
+uniform mat3 normal_matrix;
 
 in vec3 position;
 in vec3 normal;
@@ -14,6 +16,6 @@ void main() {
     vec4 hpos = vec4(position, 1.0);
     gl_Position = projection * view * model * hpos;
     world_position = vec3(model * hpos);
-    world_normal = vec3(transpose(inverse(model)) * vec4(normal, 1));
+    world_normal = normal_matrix * normal;
 }
 
@@ -5,7 +5,7 @@ uniform vec3 color_specular;
 uniform float shininess;
 uniform sampler2D awesome;
 uniform mat4 projector_view;
-uniform mat4 projection;
+uniform mat4 projector_projection;
 
 
 
@@ -39,7 +39,7 @@ void main() {
     vec3 result = (amnbient + diffuse + specular) * light_color;
     color = vec4(result, 1.0);
 
-    vec4 h_model_projector = projection * projector_view * model_world;
+    vec4 h_model_projector = projector_projection * projector_view * model_world;
     vec3 model_projector = h_model_projector.xyz / h_model_projector.w;
 
     if (all(lessThan(vec3(-1.0), model_projector)) && all(lessThan(model_projector, vec3(1.0)))) {
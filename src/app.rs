@@ -1,98 +1,753 @@
-use glium::{DisplayBuild, Surface, DrawParameters, Depth};
-use glium::draw_parameters::DepthTest;
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::path::PathBuf;
+
+use glium::{DisplayBuild, Surface, DrawParameters, Depth, Texture2d, BlitTarget};
+use glium::backend::Facade;
 use glium::backend::glutin_backend::GlutinFacade;
-use glium::glutin::{WindowBuilder, GlProfile, Event, VirtualKeyCode};
+use glium::framebuffer::SimpleFrameBuffer;
+use glium::glutin::Event;
+use glium::texture::{UncompressedFloatFormat, DepthTexture2d, MipmapsOption, DepthFormat};
+use glium::texture::{Texture2dMultisample, DepthTexture2dMultisample};
+use glium::uniforms::MagnifySamplerFilter;
 use time;
 
-use {Result, Painter, Api};
+use {Result, Oops, Api, AppConfig, Headless, Profiler, Bindings, Actions};
+use camera::{Keyframe, CameraPath, KEYFRAME_SPACING, Bookmark, Bookmarks, Projection};
+use args::Args;
+use assets::reload_programs;
+use capture::{Recorder, screenshot_path};
+use replay::{InputFrame, InputRecorder, InputReplay};
+use clock::{Clock, RealTimeClock, FixedStepClock, TimeControl};
+use golden::Golden;
+use scene::{Scene, Registry};
+use snapshot::Snapshot;
+
+
+/// Length of a simulation step passed to `Painter::update`, in seconds.
+pub const STEP: f32 = 1.0 / 60.0;
 
+/// Frames longer than this are clamped, so that a stall (for example while
+/// dragging the window) does not trigger a burst of updates.
+const MAX_FRAME_TIME: f32 = 0.25;
 
-pub struct App<P: Painter> {
+/// How often the profiler statistics are shown, in seconds.
+const REPORT_INTERVAL: f64 = 1.0;
+
+/// How often the shader files are checked for changes, in seconds.
+const RELOAD_INTERVAL: f64 = 0.5;
+
+pub struct App {
     facade: GlutinFacade,
-    painter: P,
+    registry: Registry,
+    /// Scenes are loaded when they are first shown and kept afterwards, so
+    /// switching back and forth is instant.
+    scenes: Vec<Option<Box<Scene>>>,
+    current: usize,
+    config: AppConfig,
+    profiler: Profiler,
+    time_control: TimeControl,
+    actions: Actions,
+    recorder: Option<Recorder>,
+    input_recorder: Option<InputRecorder>,
+    input_replay: Option<InputReplay>,
+    screenshot: RefCell<Option<PathBuf>>,
+    ticks: u32,
+    last_report: f64,
+    last_reload: f64,
 }
 
-impl<P: Painter> App<P> {
-    pub fn run() -> Result<()> {
-        info!("Starting the application");
-        let facade = try!(build_display());
-        let painter = try!(P::new(&facade));
-
-        let app = App {
+impl App {
+    fn new(facade: GlutinFacade,
+           config: AppConfig,
+           registry: Registry,
+           scene: usize)
+           -> Result<App> {
+        let scenes = (0..registry.len()).map(|_| None).collect();
+        let profiler = Profiler::new(&facade)
+                           .with_enabled(config.profile || config.trace.is_some());
+        let bindings = match config.bindings {
+            Some(ref path) => try!(Bindings::load(path)),
+            None => Bindings::default(),
+        };
+        let recorder = config.record.clone().map(Recorder::new);
+        let input_recorder = match config.record_input {
+            Some(ref path) => Some(try!(InputRecorder::create(path.clone()))),
+            None => None,
+        };
+        let input_replay = match config.replay {
+            Some(ref path) => Some(try!(InputReplay::load(path))),
+            None => None,
+        };
+        let mut app = App {
             facade: facade,
-            painter: painter,
+            registry: registry,
+            scenes: scenes,
+            current: scene,
+            config: config,
+            profiler: profiler,
+            time_control: TimeControl::new(),
+            actions: Actions::new(bindings),
+            recorder: recorder,
+            input_recorder: input_recorder,
+            input_replay: input_replay,
+            screenshot: RefCell::new(None),
+            ticks: 0,
+            last_report: time::precise_time_s(),
+            last_reload: time::precise_time_s(),
         };
-        try!(app.main_loop());
+        try!(app.switch_to(scene));
+        Ok(app)
+    }
+
+    /// Entry point of the launcher. The first command line argument names the
+    /// scene, the first registered one is used if it is omitted. `--golden`
+    /// without a scene checks all of them.
+    pub fn launch(registry: Registry) -> Result<()> {
+        if registry.is_empty() {
+            return Err(Oops::new("no scenes are registered"));
+        }
+        let args = Args::from_env();
+        if let Some(golden) = Golden::from_args() {
+            return App::golden(registry, try!(args.first()), golden);
+        }
+        let scene = try!(args.first()).unwrap_or(registry.name(0)).to_owned();
+        match try!(Headless::from_args()) {
+            Some(headless) => App::headless(registry, &scene, headless),
+            None => App::run(registry, &scene, try!(AppConfig::new(&*scene).from_args())),
+        }
+    }
+
+    /// Runs in real time, or with a fixed step per frame when recording.
+    pub fn run(registry: Registry, scene: &str, config: AppConfig) -> Result<()> {
+        let clock: Box<Clock> = match config.record {
+            Some(ref record) => Box::new(FixedStepClock::new(1.0 / record.fps as f64)),
+            None => Box::new(RealTimeClock::new()),
+        };
+        App::run_with_clock(registry, scene, config, clock)
+    }
+
+    pub fn run_with_clock(registry: Registry,
+                          scene: &str,
+                          config: AppConfig,
+                          clock: Box<Clock>)
+                          -> Result<()> {
+        info!("Starting the application");
+        debug!("{:?}", config);
+        let scene = try!(registry.find(scene));
+        let facade = try!(config.window_builder().build_glium());
+        let app = try!(App::new(facade, config, registry, scene));
+        try!(app.main_loop(clock));
         info!("The application has stopped");
         Ok(())
     }
 
-    fn main_loop(mut self) -> Result<()> {
+    /// Renders `headless.frames` frames into an offscreen framebuffer and
+    /// saves them as PNG files. Does not need a window or a display. The
+    /// context and multisampling flags of `AppConfig::from_args` apply.
+    pub fn headless(registry: Registry, scene: &str, headless: Headless) -> Result<()> {
+        info!("Starting the application in headless mode");
+        let scene = try!(registry.find(scene));
+        let config = try!(AppConfig::new(registry.name(scene)).from_args())
+                         .with_size(headless.width, headless.height);
+        let facade = try!(config.headless_builder().build_glium());
+        let mut app = try!(App::new(facade, config, registry, scene));
+        try!(app.render_frames(&headless));
+        info!("The application has stopped");
+        Ok(())
+    }
+
+    /// Renders the golden shots of `scene`, or of every registered scene if it
+    /// is `None`, offscreen and compares them with the reference images. With
+    /// `golden.bless` the references are rewritten instead. The size is fixed,
+    /// the other flags of `AppConfig::from_args` apply.
+    pub fn golden(registry: Registry, scene: Option<&str>, golden: Golden) -> Result<()> {
+        let scenes = match scene {
+            Some(name) => vec![try!(registry.find(name))],
+            None => (0..registry.len()).collect::<Vec<_>>(),
+        };
+        let (width, height) = (800, 600);
+        let config = try!(AppConfig::new(registry.name(scenes[0])).from_args())
+                         .with_size(width, height);
+        let facade = try!(config.headless_builder().build_glium());
+        let samples = config.multisampling;
+        let mut app = try!(App::new(facade, config, registry, scenes[0]));
+        let offscreen = try!(Offscreen::new(&app.facade, width, height, samples));
+
+        let mut failed = vec![];
+        for index in scenes {
+            try!(app.switch_to(index));
+            app.ticks = 0;
+            failed.extend(try!(app.check_golden(&golden, &offscreen)));
+        }
+
+        if !failed.is_empty() {
+            return Err(Oops::new(format!("golden images differ or are missing: {}",
+                                         failed.join(", "))));
+        }
+        Ok(())
+    }
+
+    /// Checks the golden shots of the current scene and returns the names of
+    /// the failed ones as `scene/shot`. A missing reference fails too.
+    fn check_golden(&mut self, golden: &Golden, offscreen: &Offscreen) -> Result<Vec<String>> {
+        let scene = self.registry.name(self.current);
+        info!("Running golden image tests for {}", scene);
+        let mut failed = vec![];
+        // The simulation only runs forward.
+        let mut shots = self.scene().golden_shots();
+        shots.sort_by(|a, b| a.time.partial_cmp(&b.time).unwrap_or(Ordering::Equal));
+        for shot in shots {
+            if let Some((eye, center)) = shot.camera {
+                match self.scene_mut().camera_mut() {
+                    Some(camera) => {
+                        if !camera.set_pose(eye, center) {
+                            warn!("{}: the camera cannot be moved, ignoring the pose", shot.name);
+                        }
+                    }
+                    None => warn!("{}: painter has no camera, ignoring the pose", shot.name),
+                }
+            }
+            if shot.time < self.time() {
+                warn!("{}: the simulation is already at {}", shot.name, self.time());
+            }
+            let alpha = self.advance_to(shot.time);
+            let actual = try!(self.render_offscreen(offscreen, alpha));
+            let reference = golden.reference_path(scene, &shot);
+            if golden.bless || (golden.bless_missing && !reference.exists()) {
+                try!(actual.save(&reference));
+                info!("{}: recorded {}", shot.name, reference.display());
+                continue;
+            }
+            if !reference.exists() {
+                try!(actual.save(golden.actual_path(scene, &shot)));
+                error!("{}: {} is missing, see {} and record it with --bless",
+                       shot.name,
+                       reference.display(),
+                       golden.actual_path(scene, &shot).display());
+                failed.push(format!("{}/{}", scene, shot.name));
+                continue;
+            }
+
+            let expected = try!(Snapshot::load(&reference));
+            let comparison = golden.compare(&actual, &expected);
+            info!("{}: {} of {} pixels differ, PSNR {:.2} dB, SSIM {:.4}",
+                  shot.name,
+                  comparison.mismatched,
+                  comparison.total,
+                  comparison.psnr,
+                  comparison.ssim);
+            if !golden.passes(&comparison) {
+                try!(actual.save(golden.actual_path(scene, &shot)));
+                try!(comparison.diff.save(golden.diff_path(scene, &shot)));
+                error!("{}: does not match {}, see {}",
+                       shot.name,
+                       reference.display(),
+                       golden.diff_path(scene, &shot).display());
+                failed.push(format!("{}/{}", scene, shot.name));
+            }
+        }
+        Ok(failed)
+    }
+
+    fn scene(&self) -> &Scene {
+        match self.scenes[self.current] {
+            Some(ref scene) => &**scene,
+            None => unreachable!(),
+        }
+    }
+
+    fn scene_mut(&mut self) -> &mut Scene {
+        match self.scenes[self.current] {
+            Some(ref mut scene) => &mut **scene,
+            None => unreachable!(),
+        }
+    }
+
+    /// Makes the scene at `index` current, loading it if it is shown for the
+    /// first time. The GL context and everything else is kept.
+    fn switch_to(&mut self, index: usize) -> Result<()> {
+        if self.scenes[index].is_none() {
+            let mut scene = try!(self.registry.create(index, &self.facade));
+            let (width, height) = self.facade.get_context().get_framebuffer_dimensions();
+            try!(scene.resize(&self.facade, width, height));
+            self.scenes[index] = Some(scene);
+        }
+        self.current = index;
+        Ok(())
+    }
+
+    /// Switches scenes from the keyboard. A scene which fails to load is
+    /// reported and the current one is kept.
+    fn select_scene(&mut self, index: usize) {
+        if index >= self.registry.len() || index == self.current {
+            return;
+        }
+        let name = self.registry.name(index);
+        if let Err(e) = self.switch_to(index) {
+            error!("Failed to load scene {}: {}", name, e);
+            return;
+        }
+        info!("Switched to {}", name);
+        self.config.title = name.to_owned();
+        if let Some(window) = self.facade.get_window() {
+            window.set_title(name);
+        }
+    }
+
+    fn main_loop(mut self, mut clock: Box<Clock>) -> Result<()> {
         info!("Starting the main loop");
-        let start = time::precise_time_s();
-        let mut prev_time = 0.0;
+        let mut prev_time = clock.now();
+        let mut accumulator = 0.0;
+        let mut camera_accumulator = 0.0;
         loop {
-            let time = (time::precise_time_s() - start) as f32;
-            let delta = time - prev_time;
-            prev_time = time;
             debug!("Loop iteration");
-            try!(self.draw(time));
+            let now = clock.now();
+            let frame = if self.input_replay.is_some() {
+                match self.replayed_frame() {
+                    Some(frame) => frame,
+                    None => break,
+                }
+            } else {
+                let events = self.facade.poll_events().collect();
+                let delta = ((now - prev_time) as f32).min(MAX_FRAME_TIME);
+                accumulator += self.time_control.simulation_time(delta);
+                camera_accumulator += self.time_control.camera_time(delta);
+                InputFrame {
+                    timestamp: now,
+                    events: events,
+                    updates: take_steps(&mut accumulator),
+                    alpha: accumulator / STEP,
+                    camera_updates: take_steps(&mut camera_accumulator),
+                }
+            };
+            prev_time = now;
+
+            if let Some(ref mut input) = self.input_recorder {
+                try!(input.write(&frame));
+            }
+            if try!(self.process_events(frame.events)) {
+                break;
+            }
+            for _ in 0..frame.camera_updates {
+                self.update_camera();
+            }
+            for _ in 0..frame.updates {
+                self.update();
+            }
+            try!(self.draw(frame.alpha));
+            self.report();
+            self.reload_shaders();
+        }
+        if let Some(ref path) = self.config.trace {
+            try!(self.profiler.write_trace(path));
+        }
+        Ok(())
+    }
+
+    /// The next frame of the input replay, followed by the real window events
+    /// like resizes. Real input is ignored, except for quitting.
+    fn replayed_frame(&mut self) -> Option<InputFrame> {
+        let events: Vec<Event> = self.facade.poll_events().collect();
+        if events.iter().any(|ev| is_quit(self.actions.bindings(), ev)) {
+            return None;
+        }
+        let mut frame = match self.input_replay.as_mut().and_then(|r| r.next_frame()) {
+            Some(frame) => frame,
+            None => {
+                info!("Replay finished");
+                return None;
+            }
+        };
+        frame.events.extend(events.into_iter().filter(|ev| !is_input(ev)));
+        Some(frame)
+    }
+
+    /// Compiles again the shaders changed on disk, see `reload_programs`.
+    fn reload_shaders(&mut self) {
+        let now = time::precise_time_s();
+        if now - self.last_reload < RELOAD_INTERVAL {
+            return;
+        }
+        self.last_reload = now;
+        reload_programs(&self.facade);
+    }
+
+    /// Shows the profiler statistics in the window title and in the log.
+    fn report(&mut self) {
+        let now = time::precise_time_s();
+        if !self.config.profile || now - self.last_report < REPORT_INTERVAL {
+            return;
+        }
+        self.last_report = now;
+        let summary = self.profiler.summary();
+        info!("{}", summary);
+        if let Some(window) = self.facade.get_window() {
+            window.set_title(&format!("{} | {}", self.config.title, summary));
+        }
+    }
 
-            if self.process_events(delta) {
-                return Ok(());
+    /// Simulation time, advanced only by `update`.
+    fn time(&self) -> f32 {
+        self.ticks as f32 * STEP
+    }
+
+    fn update(&mut self) {
+        match self.scenes[self.current] {
+            Some(ref mut scene) => scene.update(STEP, &self.actions),
+            None => unreachable!(),
+        }
+        self.actions.end_step();
+        self.ticks += 1;
+    }
+
+    /// Moves the camera without advancing the simulation, while it is paused.
+    fn update_camera(&mut self) {
+        match self.scenes[self.current] {
+            Some(ref mut scene) => scene.update_camera(STEP, &self.actions),
+            None => unreachable!(),
+        }
+        self.actions.end_step();
+    }
+
+    /// Runs updates until the simulation reaches `time` and returns the
+    /// interpolation factor for drawing at exactly `time`.
+    fn advance_to(&mut self, time: f32) -> f32 {
+        let (updates, alpha) = self.steps_to(time);
+        for _ in 0..updates {
+            self.update();
+        }
+        alpha
+    }
+
+    /// The number of updates needed to reach `time` and the remaining
+    /// interpolation factor.
+    fn steps_to(&self, time: f32) -> (u32, f32) {
+        let mut ticks = self.ticks;
+        while (ticks + 1) as f32 * STEP <= time {
+            ticks += 1;
+        }
+        (ticks - self.ticks, ((time - ticks as f32 * STEP) / STEP).max(0.0))
+    }
+
+    fn render_frames(&mut self, headless: &Headless) -> Result<()> {
+        let offscreen = try!(Offscreen::new(&self.facade,
+                                            headless.width,
+                                            headless.height,
+                                            self.config.multisampling));
+        for frame in 0..headless.frames {
+            let alpha = self.advance_to(headless.frame_time(frame));
+            let snapshot = try!(self.render_offscreen(&offscreen, alpha));
+            let path = headless.frame_path(frame);
+            try!(snapshot.save(&path));
+            info!("Saved {}", path.display());
+        }
+        Ok(())
+    }
+
+    fn render_offscreen(&self, offscreen: &Offscreen, alpha: f32) -> Result<Snapshot> {
+        match offscreen.multisampled {
+            Some((ref color, ref depth)) => {
+                let mut target = try!(SimpleFrameBuffer::with_depth_buffer(&self.facade,
+                                                                           color,
+                                                                           depth));
+                try!(self.draw_offscreen(&mut target, alpha));
+                let resolved = try!(SimpleFrameBuffer::new(&self.facade, &offscreen.color));
+                let (width, height) = resolved.get_dimensions();
+                let rect = BlitTarget {
+                    left: 0,
+                    bottom: 0,
+                    width: width as i32,
+                    height: height as i32,
+                };
+                target.blit_whole_color_to(&resolved, &rect, MagnifySamplerFilter::Nearest);
+            }
+            None => {
+                let mut target = try!(SimpleFrameBuffer::with_depth_buffer(&self.facade,
+                                                                           &offscreen.color,
+                                                                           &offscreen.depth));
+                try!(self.draw_offscreen(&mut target, alpha));
             }
         }
+        let snapshot = Snapshot::from_raw_image(offscreen.color.read());
+        self.profiler.end_frame();
+        Ok(snapshot)
+    }
+
+    fn draw_offscreen(&self, target: &mut SimpleFrameBuffer, alpha: f32) -> Result<()> {
+        let _frame = self.profiler.cpu_scope("frame");
+        let mut api = self.api(target, alpha);
+        self.scene().draw_offscreen(&mut api)
     }
 
-    fn draw(&self, time: f32) -> Result<()> {
+    fn draw(&mut self, alpha: f32) -> Result<()> {
         let mut target = self.facade.draw();
+        let result = {
+            let _frame = self.profiler.cpu_scope("frame");
+            let mut api = self.api(&mut target, alpha);
+            self.scene().draw_frame(&mut api)
+        };
+        try!(target.finish());
+        self.profiler.end_frame();
+        try!(result);
+        try!(self.capture());
+        Ok(())
+    }
+
+    /// Saves the finished frame if a screenshot was requested or a recording
+    /// is in progress.
+    fn capture(&mut self) -> Result<()> {
+        let screenshot = self.screenshot.borrow_mut().take();
+        if screenshot.is_none() && self.recorder.is_none() {
+            return Ok(());
+        }
+        let snapshot = Snapshot::from_raw_image(self.facade.read_front_buffer());
+        if let Some(path) = screenshot {
+            try!(snapshot.save(&path));
+            info!("Saved screenshot to {}", path.display());
+        }
+        if let Some(ref mut recorder) = self.recorder {
+            try!(recorder.write(&snapshot));
+        }
+        Ok(())
+    }
+
+    /// Clears `target` and wraps it for drawing the current scene, with the
+    /// depth test of the projection of its camera.
+    fn api<'a, S: Surface>(&'a self, target: &'a mut S, alpha: f32) -> Api<'a, S> {
         let (width, height) = target.get_dimensions();
         let aspect_ratio = width as f32 / height as f32;
-        let (r, g, b) = P::clear_color();
-        target.clear_color_and_depth((r, g, b, 1.0), 1.0);
-        let result =
-        {
-            let mut api = Api {
-                facade: &self.facade,
-                surface: &mut target,
-                time: time,
-                aspect_ratio: aspect_ratio,
-                default_params: DrawParameters {
-                    depth: Depth {
-                        test: DepthTest::IfLess,
-                        write: true,
-                        ..Default::default()
-                    },
+        let projection = self.scene().camera().map_or(Projection::default(), |c| c.projection());
+        let (r, g, b) = self.scene().clear_color();
+        target.clear_color_and_depth((r, g, b, 1.0), projection.clear_depth());
+        Api {
+            facade: &self.facade,
+            profiler: &self.profiler,
+            screenshot: &self.screenshot,
+            surface: target,
+            time: self.time(),
+            alpha: alpha,
+            aspect_ratio: aspect_ratio,
+            default_params: DrawParameters {
+                depth: Depth {
+                    test: projection.depth_test(),
+                    write: true,
                     ..Default::default()
                 },
-            };
-            self.painter.draw(&mut api)
+                ..Default::default()
+            },
+        }
+    }
+
+    /// The actions handled by the `App` itself: `screenshot`, `pause`, `step`
+    /// advances a paused simulation by one update, `slower` and `faster` scale
+    /// the time, `scene_N` switches to the N-th scene, `keyframe` records the
+    /// camera pose, `save_bookmark_N` and `bookmark_N` save and recall it.
+    fn process_action(&mut self, action: &str) {
+        match action {
+            "screenshot" => {
+                *self.screenshot.borrow_mut() = Some(screenshot_path(&self.config.title))
+            }
+            "pause" => self.time_control.toggle_pause(),
+            "step" => self.time_control.single_step(),
+            "slower" => {
+                let scale = self.time_control.scale / 2.0;
+                self.time_control.set_scale(scale)
+            }
+            "faster" => {
+                let scale = self.time_control.scale * 2.0;
+                self.time_control.set_scale(scale)
+            }
+            _ if action.starts_with("scene_") => {
+                match action["scene_".len()..].parse::<usize>() {
+                    Ok(n) if n > 0 => self.select_scene(n - 1),
+                    _ => {}
+                }
+            }
+            "keyframe" => {
+                if let Err(e) = self.record_keyframe() {
+                    error!("Failed to record a keyframe: {}", e);
+                }
+            }
+            _ if action.starts_with("save_bookmark_") => {
+                if let Ok(slot) = action["save_bookmark_".len()..].parse::<u32>() {
+                    if let Err(e) = self.save_bookmark(slot) {
+                        error!("Failed to save bookmark {}: {}", slot, e);
+                    }
+                }
+            }
+            _ if action.starts_with("bookmark_") => {
+                if let Ok(slot) = action["bookmark_".len()..].parse::<u32>() {
+                    if let Err(e) = self.recall_bookmark(slot) {
+                        error!("Failed to recall bookmark {}: {}", slot, e);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Appends the current camera pose to the camera path of the scene,
+    /// `KEYFRAME_SPACING` seconds after its last keyframe.
+    fn record_keyframe(&mut self) -> Result<()> {
+        let path = match self.config.camera_path {
+            Some(ref path) => path.clone(),
+            None => CameraPath::default_path(self.registry.name(self.current)),
         };
-        try!(target.finish());
-        try!(result);
+        let mut camera_path = if path.exists() {
+            try!(CameraPath::load(&path))
+        } else {
+            CameraPath::new()
+        };
+        let time = if camera_path.keyframes().is_empty() {
+            0.0
+        } else {
+            camera_path.duration() + KEYFRAME_SPACING
+        };
+        let keyframe = match self.scene_mut().camera_mut() {
+            Some(camera) => Keyframe::from_camera(camera, time),
+            None => return Err(Oops::new("the scene has no camera")),
+        };
+        camera_path.push(keyframe);
+        try!(camera_path.save(&path));
+        info!("Recorded a keyframe at {}s in {}", time, path.display());
+        Ok(())
+    }
+
+    /// Saves the camera pose in `slot` of the bookmarks of the scene.
+    fn save_bookmark(&mut self, slot: u32) -> Result<()> {
+        let path = Bookmarks::default_path(self.registry.name(self.current));
+        let mut bookmarks = try!(Bookmarks::load(&path));
+        let bookmark = match self.scene_mut().camera_mut() {
+            Some(camera) => Bookmark::from_camera(camera),
+            None => return Err(Oops::new("the scene has no camera")),
+        };
+        bookmarks.set(slot, bookmark);
+        try!(bookmarks.save(&path));
+        info!("Saved bookmark {} in {}", slot, path.display());
         Ok(())
     }
 
-    fn process_events(&mut self, delta: f32) -> bool {
-        for ev in self.facade.poll_events() {
+    /// Moves the camera to the pose saved in `slot`.
+    fn recall_bookmark(&mut self, slot: u32) -> Result<()> {
+        let path = Bookmarks::default_path(self.registry.name(self.current));
+        let bookmarks = try!(Bookmarks::load(&path));
+        let bookmark = match bookmarks.get(slot) {
+            Some(bookmark) => *bookmark,
+            None => return Err(Oops::new(format!("no bookmark in {}", path.display()))),
+        };
+        let applied = match self.scene_mut().camera_mut() {
+            Some(camera) => bookmark.apply(camera),
+            None => return Err(Oops::new("the scene has no camera")),
+        };
+        if applied {
+            info!("Recalled bookmark {}", slot);
+        } else {
+            warn!("Bookmark {}: the camera of the scene cannot be moved", slot);
+        }
+        Ok(())
+    }
+
+    fn process_events(&mut self, events: Vec<Event>) -> Result<bool> {
+        for ev in events {
             debug!("Event {:?}", ev);
+            if is_quit(self.actions.bindings(), &ev) {
+                return Ok(true);
+            }
+            for action in self.actions.process_event(&ev) {
+                self.process_action(&action);
+            }
             match ev {
-                Event::Closed | Event::KeyboardInput(_, _, Some(VirtualKeyCode::Escape)) => {
-                    return true;
+                Event::Resized(width, height) if width == 0 || height == 0 => {
+                    // Minimized, the render targets are kept for when the
+                    // window is shown again.
+                    debug!("Ignoring the resize to {}x{}", width, height);
+                }
+                Event::Resized(width, height) => {
+                    info!("Resized to {}x{}", width, height);
+                    // The scenes in the background too, `switch_to` only
+                    // resizes a scene when it loads it.
+                    for scene in self.scenes.iter_mut().filter_map(|scene| scene.as_mut()) {
+                        try!(scene.resize(&self.facade, width, height));
+                    }
+                    self.scene_mut().process_event(ev);
                 }
-                _ => self.painter.process_event(ev, delta),
+                _ => self.scene_mut().process_event(ev),
             }
         }
-        false
+        Ok(false)
+    }
+}
+
+/// Removes the whole steps from `accumulator` and returns how many there were.
+fn take_steps(accumulator: &mut f32) -> u32 {
+    let mut steps = 0;
+    while *accumulator >= STEP {
+        steps += 1;
+        *accumulator -= STEP;
     }
+    steps
 }
 
-fn build_display() -> Result<GlutinFacade> {
-    Ok(try!(WindowBuilder::new()
-                .with_dimensions(800, 600)
-                .with_depth_buffer(24)
-                .with_multisampling(4)
-                .with_gl_profile(GlProfile::Core)
-                .with_vsync()
-                .build_glium()))
+fn is_quit(bindings: &Bindings, event: &Event) -> bool {
+    match *event {
+        Event::Closed => true,
+        _ => bindings.triggers(event, "quit"),
+    }
+}
+
+/// Whether `event` comes from the keyboard or the mouse.
+fn is_input(event: &Event) -> bool {
+    match *event {
+        Event::KeyboardInput(..) |
+        Event::ReceivedCharacter(..) |
+        Event::MouseMoved(..) |
+        Event::MouseWheel(..) |
+        Event::MouseInput(..) => true,
+        _ => false,
+    }
+}
+
+struct Offscreen {
+    color: Texture2d,
+    depth: DepthTexture2d,
+    /// Drawn into instead when multisampling, and resolved into `color`.
+    multisampled: Option<(Texture2dMultisample, DepthTexture2dMultisample)>,
+}
+
+impl Offscreen {
+    /// Multisampled with `samples` per pixel, `0` disables it like
+    /// `AppConfig::multisampling`.
+    fn new(facade: &GlutinFacade, width: u32, height: u32, samples: u16) -> Result<Offscreen> {
+        let multisampled = if samples > 0 {
+            let samples = samples as u32;
+            Some((try!(Texture2dMultisample::empty_with_format(facade,
+                                                               UncompressedFloatFormat::U8U8U8U8,
+                                                               MipmapsOption::NoMipmap,
+                                                               width,
+                                                               height,
+                                                               samples)),
+                  try!(DepthTexture2dMultisample::empty_with_format(facade,
+                                                                    DepthFormat::F32,
+                                                                    MipmapsOption::NoMipmap,
+                                                                    width,
+                                                                    height,
+                                                                    samples))))
+        } else {
+            None
+        };
+        Ok(Offscreen {
+            multisampled: multisampled,
+            color: try!(Texture2d::empty_with_format(facade,
+                                                     UncompressedFloatFormat::U8U8U8U8,
+                                                     MipmapsOption::NoMipmap,
+                                                     width,
+                                                     height)),
+            depth: try!(DepthTexture2d::empty_with_format(facade,
+                                                          DepthFormat::F32,
+                                                          MipmapsOption::NoMipmap,
+                                                          width,
+                                                          height)),
+        })
+    }
 }
@@ -0,0 +1,99 @@
+use std::env;
+use std::error::Error;
+use std::str::FromStr;
+
+use {Result, Oops, oops};
+
+
+/// The flags followed by a value, which `Args::first` skips too.
+const VALUE_FLAGS: &'static [&'static str] = &["--assets", "--bindings", "--camera-path", "--fps",
+                                                "--frames", "--gl", "--headless", "--msaa",
+                                                "--record", "--record-input", "--replay", "--size",
+                                                "--stencil", "--step", "--time", "--title",
+                                                "--trace"];
+
+/// Minimal command line flags parsing shared by `Headless` and `AppConfig`.
+pub struct Args(Vec<String>);
+
+impl Args {
+    pub fn from_env() -> Args {
+        Args(env::args().skip(1).collect())
+    }
+
+    /// The only argument which is neither a flag nor the value of one.
+    pub fn first(&self) -> Result<Option<&str>> {
+        let mut first = None;
+        let mut args = self.0.iter();
+        while let Some(arg) = args.next() {
+            if VALUE_FLAGS.contains(&&**arg) {
+                args.next();
+            } else if !arg.starts_with("--") {
+                if first.is_some() {
+                    return Err(Oops::new(format!("unexpected argument {}", arg)));
+                }
+                first = Some(&**arg);
+            }
+        }
+        Ok(first)
+    }
+
+    pub fn has(&self, flag: &str) -> bool {
+        self.0.iter().any(|a| a == flag)
+    }
+
+    pub fn value(&self, flag: &str) -> Option<&str> {
+        self.0
+            .iter()
+            .position(|a| a == flag)
+            .and_then(|i| self.0.get(i + 1))
+            .map(|s| s.as_ref())
+    }
+
+    pub fn parse<T>(&self, flag: &str) -> Result<Option<T>>
+        where T: FromStr,
+              T::Err: Error + 'static
+    {
+        match self.value(flag) {
+            None => Ok(None),
+            Some(value) => {
+                value.parse()
+                     .map(Some)
+                     .map_err(|e| oops(format!("invalid {} {}", flag, value), e))
+            }
+        }
+    }
+
+    /// Parses a `WIDTHxHEIGHT` value.
+    pub fn size(&self, flag: &str) -> Result<Option<(u32, u32)>> {
+        let value = match self.value(flag) {
+            Some(value) => value,
+            None => return Ok(None),
+        };
+        let pair = try!(parse_pair(value, 'x').ok_or_else(|| {
+            Oops::new(format!("invalid {} {}, expected WIDTHxHEIGHT", flag, value))
+        }));
+        Ok(Some(pair))
+    }
+
+    /// Parses a `MAJOR.MINOR` value.
+    pub fn version(&self, flag: &str) -> Result<Option<(u8, u8)>> {
+        let value = match self.value(flag) {
+            Some(value) => value,
+            None => return Ok(None),
+        };
+        let pair = try!(parse_pair(value, '.').ok_or_else(|| {
+            Oops::new(format!("invalid {} {}, expected MAJOR.MINOR", flag, value))
+        }));
+        Ok(Some(pair))
+    }
+}
+
+fn parse_pair<T: FromStr>(value: &str, separator: char) -> Option<(T, T)> {
+    let mut parts = value.split(separator);
+    let first = parts.next().and_then(|p| p.parse().ok());
+    let second = parts.next().and_then(|p| p.parse().ok());
+    match (first, second, parts.next()) {
+        (Some(first), Some(second), None) => Some((first, second)),
+        _ => None,
+    }
+}
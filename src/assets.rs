@@ -1,37 +1,228 @@
-use std::fs::File;
+use std::cell::{Cell, Ref, RefCell};
+use std::env;
+use std::fs::{self, File};
 use std::io::prelude::*;
 use std::io::Cursor;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::rc::{Rc, Weak};
+use std::time::SystemTime;
 
-use glium::Program;
-use glium::backend::Facade;
+use glium::{Program, Surface, Texture2d, BlitTarget};
 use glium::backend::glutin_backend::GlutinFacade;
+use glium::framebuffer::SimpleFrameBuffer;
 use glium::texture::cubemap::Cubemap;
-use glium::texture::{UncompressedFloatFormat, MipmapsOption, Dimensions, RawImage2d};
-use gl;
+use glium::texture::{UncompressedFloatFormat, MipmapsOption, RawImage2d, CubeLayer};
+use glium::uniforms::MagnifySamplerFilter;
+use glium::program::ProgramCreationError;
 use image;
 
-use {Result, oops};
+use {Result, Oops, oops};
+use args::Args;
 
 type RawImage = RawImage2d<'static, u8>;
 
+/// Extra asset directories, separated like `PATH`.
+pub const ASSETS_VAR: &'static str = "LIGHTS_ASSETS";
+
+thread_local!(static CURRENT: RefCell<AssetManager> = RefCell::new(AssetManager::from_env()));
+
+thread_local!(static WATCHED: RefCell<Vec<Weak<Watched>>> = RefCell::new(Vec::new()));
+
+
+/// Directories searched in order for assets.
+#[derive(Debug, Clone)]
+pub struct AssetManager {
+    roots: Vec<PathBuf>,
+}
+
+impl AssetManager {
+    pub fn new() -> AssetManager {
+        AssetManager { roots: Vec::new() }
+    }
+
+    pub fn with_root<P: Into<PathBuf>>(mut self, root: P) -> AssetManager {
+        let root = root.into();
+        if !self.roots.contains(&root) {
+            self.roots.push(root);
+        }
+        self
+    }
+
+    /// `--assets DIR`, `LIGHTS_ASSETS`, then the existing `assets` next to the
+    /// executable and in this crate.
+    pub fn from_env() -> AssetManager {
+        let mut result = AssetManager::new();
+        if let Some(dir) = Args::from_env().value("--assets") {
+            result = result.with_root(dir);
+        }
+        if let Some(dirs) = env::var_os(ASSETS_VAR) {
+            for dir in env::split_paths(&dirs) {
+                result = result.with_root(dir);
+            }
+        }
+        let exe_dir = env::current_exe()
+                          .ok()
+                          .and_then(|exe| exe.parent().map(|dir| dir.join("assets")));
+        let crate_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("assets");
+        for dir in exe_dir.into_iter().chain(Some(crate_dir)) {
+            if dir.is_dir() {
+                result = result.with_root(dir);
+            }
+        }
+        result
+    }
+
+    /// The installed manager, else `from_env`.
+    pub fn current() -> AssetManager {
+        CURRENT.with(|current| current.borrow().clone())
+    }
+
+    /// Makes the loaders of this thread use this manager.
+    pub fn install(self) {
+        CURRENT.with(|current| *current.borrow_mut() = self)
+    }
+
+    pub fn roots(&self) -> &[PathBuf] {
+        &self.roots
+    }
+
+    pub fn find<P: AsRef<Path>>(&self, path: P) -> Result<PathBuf> {
+        let path = path.as_ref();
+        if let Some(found) = self.roots.iter().map(|root| root.join(path)).find(|p| p.exists()) {
+            return Ok(found);
+        }
+        let searched = if self.roots.is_empty() {
+            "there are no asset directories".to_owned()
+        } else {
+            let roots = self.roots
+                            .iter()
+                            .map(|root| root.display().to_string())
+                            .collect::<Vec<_>>();
+            format!("not found in any of {}", roots.join(", "))
+        };
+        Err(Oops::new(format!("{}: {}, add a directory with --assets DIR or {}",
+                              path.display(),
+                              searched,
+                              ASSETS_VAR)))
+    }
+
+    /// Where to write `path`, the found file or else in the first root.
+    pub fn locate<P: AsRef<Path>>(&self, path: P) -> PathBuf {
+        let path = path.as_ref();
+        self.find(path).unwrap_or_else(|_| {
+            self.roots.first().map_or(Path::new("./assets"), |root| root.as_path()).join(path)
+        })
+    }
+}
+
+
+/// Compiled again by `reload_programs` when its shaders change.
+pub struct WatchedProgram(Rc<Watched>);
+
+struct Watched {
+    vertex_shader: PathBuf,
+    fragment_shader: PathBuf,
+    modified: Cell<Option<[SystemTime; 2]>>,
+    program: RefCell<Program>,
+}
+
+impl WatchedProgram {
+    /// The last version of the shaders which compiled.
+    pub fn get(&self) -> Ref<Program> {
+        self.0.program.borrow()
+    }
+}
+
+impl Watched {
+    fn reload_if_changed(&self, facade: &GlutinFacade) {
+        // Missing while an editor saves the file, the next poll gets it.
+        let modified = match self.modification_times() {
+            Some(modified) => modified,
+            None => return,
+        };
+        // Any change of either file counts, checking out an older version too.
+        if self.modified.get() == Some(modified) {
+            return;
+        }
+        self.modified.set(Some(modified));
+        match compile(facade, &self.vertex_shader, &self.fragment_shader) {
+            Ok(program) => {
+                *self.program.borrow_mut() = program;
+                info!("Reloaded {} and {}",
+                      self.vertex_shader.display(),
+                      self.fragment_shader.display());
+            }
+            Err(e) => error!("{}, keeping the previous program", e.message()),
+        }
+    }
+
+    fn modification_times(&self) -> Option<[SystemTime; 2]> {
+        match (modified(&self.vertex_shader), modified(&self.fragment_shader)) {
+            (Some(vertex), Some(fragment)) => Some([vertex, fragment]),
+            _ => None,
+        }
+    }
+}
 
 pub fn load_program(facade: &GlutinFacade,
                     vertex_shader_path: &str,
                     fragment_shader_path: &str)
-                    -> Result<Program> {
+                    -> Result<WatchedProgram> {
+
+    let assets = AssetManager::current();
+    let shaders = Path::new("shaders");
+    let vertex_shader = try!(assets.find(shaders.join(vertex_shader_path)));
+    let fragment_shader = try!(assets.find(shaders.join(fragment_shader_path)));
+    let program = try!(compile(facade, &vertex_shader, &fragment_shader));
+    let watched = Rc::new(Watched {
+        vertex_shader: vertex_shader,
+        fragment_shader: fragment_shader,
+        modified: Cell::new(None),
+        program: RefCell::new(program),
+    });
+    watched.modified.set(watched.modification_times());
+    WATCHED.with(|programs| programs.borrow_mut().push(Rc::downgrade(&watched)));
+    Ok(WatchedProgram(watched))
+}
+
+/// A program which fails to compile is kept as it was.
+pub fn reload_programs(facade: &GlutinFacade) {
+    WATCHED.with(|programs| {
+        let mut programs = programs.borrow_mut();
+        programs.retain(|program| program.upgrade().is_some());
+        for program in programs.iter().filter_map(|program| program.upgrade()) {
+            program.reload_if_changed(facade);
+        }
+    })
+}
+
+fn compile(facade: &GlutinFacade, vertex_shader: &Path, fragment_shader: &Path) -> Result<Program> {
+    let vertex_source = try!(slurp(vertex_shader));
+    let fragment_source = try!(slurp(fragment_shader));
+    Program::from_source(facade, &vertex_source, &fragment_source, None).map_err(|e| {
+        let log = match e {
+            ProgramCreationError::CompilationError(ref log) |
+            ProgramCreationError::LinkingError(ref log) => log.clone(),
+            ref e => format!("{:?}", e),
+        };
+        Oops::new(format!("failed to compile {} and {}:\n{}",
+                          vertex_shader.display(),
+                          fragment_shader.display(),
+                          log))
+    })
+}
 
-    let vertex_shader = try!(slurp(&format!("./assets/shaders/{}", vertex_shader_path)));
-    let fragment_shader = try!(slurp(&format!("./assets/shaders/{}", fragment_shader_path)));
-    Ok(try!(Program::from_source(facade, &vertex_shader, &fragment_shader, None)))
+fn modified(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|m| m.modified()).ok()
 }
 
 fn load_cubemap_faces(texture_src: &str) -> Result<(u32, Vec<RawImage>)> {
     let parts = ["right", "left", "bottom", "top", "back", "front"];
+    let assets = AssetManager::current();
     let mut size = 0;
     parts.iter()
          .map(|part| {
-             let path = &format!("./assets/textures/{}/{}.jpg", texture_src, part);
+             let path = try!(assets.find(format!("textures/{}/{}.jpg", texture_src, part)));
              let cursor = Cursor::new(try!(slurp_bytes(path)));
              let im = try!(image::load(cursor, image::JPEG)).to_rgba();
              let dim = im.dimensions();
@@ -51,65 +242,35 @@ fn load_cubemap_faces(texture_src: &str) -> Result<(u32, Vec<RawImage>)> {
 pub fn load_cubemap(facade: &GlutinFacade, texture_src: &str) -> Result<Cubemap> {
     info!("Loading cubemap {} ...", texture_src);
     let (size, faces) = try!(load_cubemap_faces(texture_src));
+    let layers = [CubeLayer::PositiveX,
+                  CubeLayer::NegativeX,
+                  CubeLayer::PositiveY,
+                  CubeLayer::NegativeY,
+                  CubeLayer::PositiveZ,
+                  CubeLayer::NegativeZ];
 
-    let result = unsafe {
-        let mut id = 0;
-        facade.get_context().exec_in_context(|| {
-            let window = facade.get_window().expect("can't load cubemap in headless context");
-            gl::load_with(|s| window.get_proc_address(s) as *const _);
-
-            id = cubemap_id(faces, size)
-        });
-        debug!("Cubemap id {}", id);
-        Cubemap::from_id(facade,
-                         UncompressedFloatFormat::U8U8U8U8,
-                         id,
-                         true,
-                         MipmapsOption::NoMipmap,
-                         Dimensions::Cubemap { dimension: size })
+    let result = try!(Cubemap::empty_with_format(facade,
+                                                 UncompressedFloatFormat::U8U8U8U8,
+                                                 MipmapsOption::NoMipmap,
+                                                 size));
+    // Upload each face as a regular texture and blit it into the cubemap, so that
+    // this works with any context, including headless ones.
+    let target = BlitTarget {
+        left: 0,
+        bottom: 0,
+        width: size as i32,
+        height: size as i32,
     };
+    for (&layer, face) in layers.iter().zip(faces.into_iter()) {
+        let texture = try!(Texture2d::new(facade, face));
+        let framebuffer = try!(SimpleFrameBuffer::new(facade, result.main_level().image(layer)));
+        texture.as_surface()
+               .blit_whole_color_to(&framebuffer, &target, MagnifySamplerFilter::Linear);
+    }
     info!("    ...Done!");
     Ok(result)
 }
 
-unsafe fn cubemap_id(faces: Vec<RawImage>, size: u32) -> u32 {
-    let mut result: u32 = 0;
-    gl::GenTextures(1, &mut result);
-    gl::BindTexture(gl::TEXTURE_CUBE_MAP, result);
-    for (i, face) in faces.iter().enumerate() {
-        let bind_point = gl::TEXTURE_CUBE_MAP_POSITIVE_X + i as u32;
-        let size = size as i32;
-        gl::TexImage2D(bind_point,
-                       0,
-                       gl::RGBA as i32,
-                       size,
-                       size,
-                       0,
-                       gl::RGBA,
-                       gl::UNSIGNED_BYTE,
-                       face.data.as_ptr() as *const _);
-    }
-
-    gl::TexParameteri(gl::TEXTURE_CUBE_MAP,
-                      gl::TEXTURE_MAG_FILTER,
-                      gl::LINEAR as i32);
-    gl::TexParameteri(gl::TEXTURE_CUBE_MAP,
-                      gl::TEXTURE_MIN_FILTER,
-                      gl::LINEAR as i32);
-    gl::TexParameteri(gl::TEXTURE_CUBE_MAP,
-                      gl::TEXTURE_WRAP_S,
-                      gl::CLAMP_TO_EDGE as i32);
-    gl::TexParameteri(gl::TEXTURE_CUBE_MAP,
-                      gl::TEXTURE_WRAP_T,
-                      gl::CLAMP_TO_EDGE as i32);
-    gl::TexParameteri(gl::TEXTURE_CUBE_MAP,
-                      gl::TEXTURE_WRAP_R,
-                      gl::CLAMP_TO_EDGE as i32);
-    gl::BindTexture(gl::TEXTURE_CUBE_MAP, 0);
-
-    result
-}
-
 pub fn slurp<P: AsRef<Path>>(path: P) -> Result<String> {
     let name = path.as_ref().display().to_string();
     let bytes = try!(slurp_bytes(path));
@@ -126,11 +287,18 @@ fn slurp_bytes<P: AsRef<Path>>(path: P) -> Result<Vec<u8>> {
     Ok(data)
 }
 
-pub fn load_texture<P: AsRef<Path>>(path: P) -> RawImage2d<'static, u8> {
-    let bytes = slurp_bytes(path).expect("Failed to load a texture");
-    let im = image::load(Cursor::new(bytes), image::PNG)
-                 .expect("Failed to load a texture")
+pub fn load_texture(name: &str) -> Result<RawImage> {
+    let path = try!(AssetManager::current().find(Path::new("textures").join(name)));
+    load_texture_file(path)
+}
+
+/// Without searching the asset roots.
+pub fn load_texture_file<P: AsRef<Path>>(path: P) -> Result<RawImage> {
+    let name = path.as_ref().display().to_string();
+    let bytes = try!(slurp_bytes(path));
+    let im = try!(image::load(Cursor::new(bytes), image::PNG)
+                      .map_err(|e| oops(format!("failed to decode {}", name), e)))
                  .to_rgba();
     let dim = im.dimensions();
-    RawImage2d::from_raw_rgba_reversed(im.into_raw(), dim)
+    Ok(RawImage2d::from_raw_rgba_reversed(im.into_raw(), dim))
 }
@@ -0,0 +1,173 @@
+use glium::{Surface, Texture2d};
+use glium::texture::{UncompressedFloatFormat, DepthTexture2d, MipmapsOption, DepthFormat};
+use glium::backend::glutin_backend::GlutinFacade;
+use glium::glutin::Event;
+use glium::framebuffer::MultiOutputFrameBuffer;
+
+use lights::{Shot, Painter, Api, Camera, UserCamera, Model, load_program, WatchedProgram, Result,
+             Actions};
+use lights::math::*;
+
+mod quad;
+
+use self::quad::Quad;
+
+pub struct Bacon {
+    camera: UserCamera,
+    time: f32,
+    light: Vec3,
+    previous_light: Vec3,
+    suite: Model,
+    program: WatchedProgram,
+    quad: Quad,
+    g_buffer: GBuffer,
+}
+
+struct GBuffer {
+    albedo: Texture2d,
+    specular_shininess: Texture2d,
+    position: Texture2d,
+    normal: Texture2d,
+    depth: DepthTexture2d,
+}
+
+impl GBuffer {
+    fn new(facade: &GlutinFacade, width: u32, height: u32) -> Result<GBuffer> {
+        let make_texture = || {
+            Texture2d::empty_with_format(facade,
+                                         UncompressedFloatFormat::F32F32F32F32,
+                                         MipmapsOption::NoMipmap,
+                                         width,
+                                         height)
+        };
+
+        Ok(GBuffer {
+            albedo: try!(make_texture()),
+            specular_shininess: try!(make_texture()),
+            position: try!(make_texture()),
+            normal: try!(make_texture()),
+            depth: try!(DepthTexture2d::empty_with_format(facade,
+                                                          DepthFormat::F32,
+                                                          MipmapsOption::NoMipmap,
+                                                          width,
+                                                          height)),
+        })
+    }
+
+    fn buffer(&self, facade: &GlutinFacade) -> Result<MultiOutputFrameBuffer> {
+        let output = &[("albedo", &self.albedo),
+                       ("specular_shininess", &self.specular_shininess),
+                       ("position", &self.position),
+                       ("normal", &self.normal)];
+        let buffer = try!(MultiOutputFrameBuffer::with_depth_buffer(facade,
+                                                                    output.iter().cloned(),
+                                                                    &self.depth));
+        Ok(buffer)
+    }
+}
+
+impl Painter for Bacon {
+    fn new(facade: &GlutinFacade) -> Result<Bacon> {
+        let suite = try!(Model::load(facade, "nanosuit/nanosuit.obj"));
+        Ok(Bacon {
+            camera: UserCamera::new(vec3(0.0, 1.0, 3.0), vec3(0.0, 1.0, 0.0), Y),
+            time: 0.0,
+            light: light_position(0.0),
+            previous_light: light_position(0.0),
+            program: try!(load_program(facade, "army/geom/vertex.glsl", "army/geom/fragment.glsl")),
+            suite: suite,
+            quad: try!(Quad::new(facade)),
+            g_buffer: try!(GBuffer::new(facade, 800, 600)),
+        })
+    }
+
+    fn process_event(&mut self, event: Event) {
+        self.camera.process_event(event)
+    }
+
+    fn update(&mut self, dt: f32, actions: &Actions) {
+        self.camera.update(dt, actions);
+        self.time += dt;
+        self.previous_light = self.light;
+        self.light = light_position(self.time);
+    }
+
+    fn resize(&mut self, facade: &GlutinFacade, width: u32, height: u32) -> Result<()> {
+        self.camera.resize(width, height);
+        self.g_buffer = try!(GBuffer::new(facade, width, height));
+        Ok(())
+    }
+
+    fn camera(&self) -> Option<&Camera> {
+        Some(&self.camera)
+    }
+
+    fn camera_mut(&mut self) -> Option<&mut Camera> {
+        Some(&mut self.camera)
+    }
+
+    fn golden_shots() -> Vec<Shot> {
+        vec![Shot::new("front", 0.0),
+             Shot::new("overview", 1.0).looking(vec3(0.0, 3.0, 9.0), vec3(0.0, -3.0, 0.0)),
+             Shot::new("orbit", 2.0)]
+    }
+
+    fn draw<S: Surface>(&self, api: &mut Api<S>) -> Result<()> {
+        let mut g_buffer = try!(self.g_buffer.buffer(api.facade));
+        let depth = self.camera.projection().clear_depth();
+        g_buffer.clear_color_and_depth((0.0, 0.0, 0.0, 1.0), depth);
+
+        let positions = [vec3(0.0, -3.0, -3.0),
+                         vec3(3.0, -3.0, -3.0),
+                         vec3(-3.0, -3.0, 0.0),
+                         vec3(0.0, -3.0, 0.0),
+                         vec3(3.0, -3.0, 0.0),
+                         vec3(-3.0, -3.0, 3.0),
+                         vec3(0.0, -3.0, 3.0),
+                         vec3(3.0, -3.0, 3.0)];
+
+        {
+            let scope = api.profiler.scope("g-buffer");
+            let params = scope.params(&api.default_params);
+            for &pos in positions.into_iter() {
+                let model = id().translate(pos).scale(0.2);
+                let uniforms = uniform! {
+                    model: model,
+                    view: self.camera.view(),
+                    projection: api.projection(&self.camera),
+                    normal_matrix: (self.camera.view() * model).normal_matrix(),
+                };
+
+                let frustum = api.frustum(&self.camera, model);
+                let count = try!(self.suite.draw(&mut g_buffer,
+                                                 &params,
+                                                 &self.program.get(),
+                                                 &uniforms,
+                                                 &frustum));
+                scope.count_draws(count.drawn);
+                scope.count_culled(count.culled);
+            }
+        }
+
+        let light_position = Unif(self.previous_light.lerp(self.light, api.alpha));
+
+        let uniforms = uniform! {
+            albedo: &self.g_buffer.albedo,
+            specular_shininess: &self.g_buffer.specular_shininess,
+            position: &self.g_buffer.position,
+            normal: &self.g_buffer.normal,
+            view: self.camera.view(),
+            projection: api.projection(&self.camera),
+            light: light_position,
+            light_color: Color::gray(0.2),
+        };
+        let scope = api.profiler.scope("lighting");
+        scope.count_draws(1);
+        self.quad.draw(api.surface, &scope.params(&api.default_params), &uniforms)
+    }
+}
+
+fn light_position(time: f32) -> Vec3 {
+    let radius = 8.0;
+    vec3(time.sin() * radius, 2.0 * time.sin(), time.cos() * radius)
+}
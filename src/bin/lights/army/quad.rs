@@ -1,10 +1,10 @@
-use glium::{Surface, Program, VertexBuffer, IndexBuffer, DrawParameters};
+use glium::{Surface, VertexBuffer, IndexBuffer, DrawParameters};
 use glium::uniforms::Uniforms;
 use glium::index::PrimitiveType;
 use glium::backend::glutin_backend::GlutinFacade;
 
 
-use lights::{Result, load_program};
+use lights::{Result, load_program, WatchedProgram};
 
 
 #[derive(Copy, Clone)]
@@ -18,7 +18,7 @@ implement_vertex!(Vertex, position, texture);
 pub struct Quad {
     vertex_buffer: VertexBuffer<Vertex>,
     index_buffer: IndexBuffer<u16>,
-    program: Program,
+    program: WatchedProgram,
 }
 
 impl Quad {
@@ -63,7 +63,7 @@ impl Quad {
 
         Ok(try!(surface.draw(&self.vertex_buffer,
                              &self.index_buffer,
-                             &self.program,
+                             &self.program.get(),
                              uniforms,
                              params)))
     }
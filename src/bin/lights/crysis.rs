@@ -0,0 +1,116 @@
+use glium::Surface;
+use glium::backend::glutin_backend::GlutinFacade;
+use glium::glutin::Event;
+
+use lights::{Shot, Painter, Api, Camera, UserCamera, Model, load_program, WatchedProgram, Result,
+             Actions};
+use lights::math::*;
+
+pub struct Bacon {
+    camera: UserCamera,
+    window_size: (u32, u32),
+    time: f32,
+    light: Vec3,
+    previous_light: Vec3,
+    suite: Model,
+    program: WatchedProgram,
+}
+
+impl Painter for Bacon {
+    fn new(facade: &GlutinFacade) -> Result<Bacon> {
+        let suite = try!(Model::load(facade, "nanosuit/nanosuit.obj"));
+
+        Ok(Bacon {
+            camera: UserCamera::new(vec3(0.0, 1.0, 3.0), vec3(0.0, 1.0, 0.0), Y),
+            window_size: (800, 600),
+            time: 0.0,
+            light: light_position(0.0),
+            previous_light: light_position(0.0),
+            program: try!(load_program(facade, "suit/vertex.glsl", "suit/fragment.glsl")),
+            suite: suite,
+        })
+    }
+
+    fn process_event(&mut self, event: Event) {
+        self.camera.process_event(event)
+    }
+
+    fn update(&mut self, dt: f32, actions: &Actions) {
+        if actions.pressed("pick") {
+            if let Some(cursor) = actions.cursor() {
+                self.pick(cursor);
+            }
+        }
+        self.update_camera(dt, actions);
+        self.time += dt;
+        self.previous_light = self.light;
+        self.light = light_position(self.time);
+    }
+
+    fn update_camera(&mut self, dt: f32, actions: &Actions) {
+        if actions.pressed("frame") {
+            let bounds = self.suite.bounds();
+            self.camera.frame(bounds.min * 0.1, bounds.max * 0.1);
+        }
+        self.camera.update(dt, actions);
+    }
+
+    fn resize(&mut self, _facade: &GlutinFacade, width: u32, height: u32) -> Result<()> {
+        self.window_size = (width, height);
+        self.camera.resize(width, height);
+        Ok(())
+    }
+
+    fn camera(&self) -> Option<&Camera> {
+        Some(&self.camera)
+    }
+
+    fn camera_mut(&mut self) -> Option<&mut Camera> {
+        Some(&mut self.camera)
+    }
+
+    fn golden_shots() -> Vec<Shot> {
+        vec![Shot::new("front", 0.0),
+             Shot::new("helmet", 0.5).looking(vec3(0.5, 1.6, 1.0), vec3(0.0, 1.5, 0.0)),
+             Shot::new("orbit", 1.5)]
+    }
+
+    fn draw<S: Surface>(&self, api: &mut Api<S>) -> Result<()> {
+        let light_position = Unif(self.previous_light.lerp(self.light, api.alpha));
+        let model = id().scale(0.1);
+        let uniforms = uniform! {
+            model: model,
+            view: self.camera.view(),
+            projection: api.projection(&self.camera),
+            normal_matrix: (self.camera.view() * model).normal_matrix(),
+            light: light_position,
+        };
+        let frustum = api.frustum(&self.camera, model);
+        let program = self.program.get();
+        let scope = api.profiler.scope("suit");
+        let params = scope.params(&api.default_params);
+        let count = try!(self.suite.draw(api.surface, &params, &program, &uniforms, &frustum));
+        scope.count_draws(count.drawn);
+        scope.count_culled(count.culled);
+        Ok(())
+    }
+}
+
+impl Bacon {
+    /// Logs the part of the suit under `cursor`.
+    fn pick(&self, cursor: (i32, i32)) {
+        let ray = self.camera.ray(cursor, self.window_size);
+        match self.suite.pick(&ray, id().scale(0.1)) {
+            Some(hit) => {
+                let material = hit.material.map_or("no material", |m| &m.name[..]);
+                info!("Picked {} ({}) at {:?}", hit.name, material, hit.position)
+            }
+            None => info!("Picked nothing"),
+        }
+    }
+}
+
+fn light_position(time: f32) -> Vec3 {
+    let radius = 8.0;
+    vec3(time.sin() * radius, 2.0 * time.sin(), time.cos() * radius)
+}
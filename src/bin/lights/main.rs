@@ -0,0 +1,43 @@
+#[macro_use]
+extern crate glium;
+#[macro_use]
+extern crate log;
+extern crate env_logger;
+extern crate lights;
+
+use std::io::prelude::*;
+
+use env_logger::LogBuilder;
+
+use lights::{App, Registry};
+
+mod mirror;
+mod crysis;
+mod spot;
+mod army;
+
+fn init_log() {
+    LogBuilder::new()
+        .parse("info")
+        .init()
+        .expect("Failed to init the logger");
+}
+
+fn scenes() -> Registry {
+    Registry::new()
+        .register::<mirror::Matisse>("mirror")
+        .register::<crysis::Bacon>("crysis")
+        .register::<spot::Bacon>("spot")
+        .register::<army::Bacon>("army")
+}
+
+fn main() {
+    init_log();
+    if let Err(e) = App::launch(scenes()) {
+        writeln!(std::io::stderr(), "{}\n=(", e).unwrap();
+        if let Some(info) = e.guru_info() {
+            writeln!(std::io::stderr(), "\nGuru meditation:\n{}", info).unwrap();
+        }
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,144 @@
+use glium::backend::glutin_backend::GlutinFacade;
+use glium::index::{NoIndices, PrimitiveType};
+use glium::{Surface, VertexBuffer, DrawParameters, Depth};
+use glium::glutin::Event;
+use glium::texture::cubemap::Cubemap;
+
+use lights::{Shot, Api, Painter, load_program, WatchedProgram, Camera, UserCamera, load_cubemap,
+             Model, Result, Actions};
+use lights::math::*;
+
+mod vertex;
+mod models;
+
+use self::vertex::Vertex;
+
+pub struct Matisse {
+    camera: UserCamera,
+    skybox: SkyBox,
+    bunny: Model,
+    cube: Model,
+    program: WatchedProgram,
+}
+
+impl Painter for Matisse {
+    fn new(facade: &GlutinFacade) -> Result<Matisse> {
+
+        Ok(Matisse {
+            camera: UserCamera::new(vec3(0.0, 0.0, 3.0), vec3(0.0, 0.0, 0.0), Y),
+            skybox: try!(SkyBox::new(facade)),
+            bunny: try!(Model::load(facade, "bunny_with_normals.obj")),
+            cube: try!(Model::load(facade, "cube.obj")),
+            program: try!(load_program(facade, "mirror/vertex.glsl", "mirror/fragment.glsl")),
+        })
+    }
+
+    fn process_event(&mut self, event: Event) {
+        self.camera.process_event(event)
+    }
+
+    fn update(&mut self, dt: f32, actions: &Actions) {
+        self.update_camera(dt, actions)
+    }
+
+    fn update_camera(&mut self, dt: f32, actions: &Actions) {
+        if actions.pressed("frame") {
+            let bounds = self.bunny.bounds();
+            self.camera.frame(bounds.min * 5.0, bounds.max * 5.0);
+        }
+        self.camera.update(dt, actions)
+    }
+
+    fn resize(&mut self, _facade: &GlutinFacade, width: u32, height: u32) -> Result<()> {
+        self.camera.resize(width, height);
+        Ok(())
+    }
+
+    fn camera(&self) -> Option<&Camera> {
+        Some(&self.camera)
+    }
+
+    fn camera_mut(&mut self) -> Option<&mut Camera> {
+        Some(&mut self.camera)
+    }
+
+    fn golden_shots() -> Vec<Shot> {
+        vec![Shot::new("front", 0.0),
+             Shot::new("side", 0.0).looking(vec3(2.5, 1.0, 1.5), vec3(0.0, 0.0, 0.0)),
+             Shot::new("above", 0.0).looking(vec3(0.0, 3.0, 1.0), vec3(0.0, 0.0, 0.0))]
+    }
+
+    fn draw<S: Surface>(&self, api: &mut Api<S>) -> Result<()> {
+        try!(self.skybox.draw(api, self));
+        let model = id().scale(5.0);
+        let uniforms = uniform! {
+            model: model,
+            view: self.camera.view(),
+            projection: api.projection(&self.camera),
+            normal_matrix: model.normal_matrix(),
+            camera_position: Unif(self.camera.position()),
+            skybox: &self.skybox.cubemap,
+        };
+        let frustum = api.frustum(&self.camera, model);
+        let program = self.program.get();
+        let scope = api.profiler.scope("models");
+        let params = scope.params(&api.default_params);
+        let count = try!(self.bunny.draw(api.surface, &params, &program, &uniforms, &frustum));
+        scope.count_draws(count.drawn);
+        scope.count_culled(count.culled);
+
+        let model = id().translate(vec3(0.0, -0.3, 0.0));
+        let uniforms = uniform! {
+            model: model,
+            view: self.camera.view(),
+            projection: api.projection(&self.camera),
+            normal_matrix: model.normal_matrix(),
+            camera_position: Unif(self.camera.position()),
+            skybox: &self.skybox.cubemap,
+        };
+        let frustum = api.frustum(&self.camera, model);
+        let count = try!(self.cube.draw(api.surface, &params, &program, &uniforms, &frustum));
+        scope.count_draws(count.drawn);
+        scope.count_culled(count.culled);
+
+        Ok(())
+    }
+}
+
+struct SkyBox {
+    vertex_buffer: VertexBuffer<Vertex>,
+    program: WatchedProgram,
+    cubemap: Cubemap,
+}
+
+impl SkyBox {
+    fn new(facade: &GlutinFacade) -> Result<SkyBox> {
+        let shape = Vertex::many(models::skybox());
+        Ok(SkyBox {
+            vertex_buffer: try!(VertexBuffer::new(facade, &shape)),
+            program: try!(load_program(facade, "skybox/vertex.glsl", "skybox/fragment.glsl")),
+            cubemap: try!(load_cubemap(facade, "skybox")),
+        })
+    }
+
+    fn draw<S: Surface>(&self,
+                        api: &mut Api<S>,
+                        p: &Matisse)
+                        -> Result<()> {
+        let uniforms = uniform! {
+            view: p.camera.view(),
+            projection: api.projection(&p.camera),
+            skybox: &self.cubemap,
+        };
+
+        try!(api.surface.draw(&self.vertex_buffer,
+                              &NoIndices(PrimitiveType::TrianglesList),
+                              &self.program.get(),
+                              &uniforms,
+                              &DrawParameters {
+                                  depth: Depth { write: false, ..Default::default() },
+                                  ..api.default_params.clone()
+                              }));
+        Ok(())
+    }
+}
@@ -0,0 +1,181 @@
+use glium::{Surface, VertexBuffer, Texture2d};
+use glium::draw_parameters::{DrawParameters, PolygonMode};
+use glium::index::{NoIndices, PrimitiveType};
+use glium::backend::glutin_backend::GlutinFacade;
+use glium::glutin::Event;
+
+use lights::{Painter, Api, Model, Camera, FlyCamera, PathCamera, CameraPath, Projection,
+             load_program, WatchedProgram, Result, load_texture, Actions};
+use lights::math::*;
+
+mod models;
+mod vertex;
+
+pub struct Bacon {
+    /// Flies through `assets/paths/spot.txt`, the mouse and keys move the
+    /// projector.
+    viewer: PathCamera,
+    projector: Projector,
+    ruins: Model,
+    awesome: Texture2d,
+    program: WatchedProgram,
+}
+
+impl Painter for Bacon {
+    fn new(facade: &GlutinFacade) -> Result<Bacon> {
+        let ruins = try!(Model::load(facade, "ruins/house.obj"));
+        let awesome = try!(load_texture("awesomeface.png"));
+        let path = try!(CameraPath::load(CameraPath::default_path("spot")));
+        Ok(Bacon {
+            viewer: try!(PathCamera::new(path)),
+            projector: try!(Projector::new(facade)),
+            ruins: ruins,
+            awesome: try!(Texture2d::new(facade, awesome)),
+            program: try!(load_program(facade, "ruins/vertex.glsl", "ruins/fragment.glsl")),
+        })
+    }
+
+    fn draw<S: Surface>(&self, api: &mut Api<S>) -> Result<()> {
+        let model = id().translate(vec3(0.0, -2.0, 0.0));
+        let uniforms = uniform! {
+            model: model,
+            view: self.viewer.view(),
+            projection: api.projection(&self.viewer),
+            normal_matrix: (self.viewer.view() * model).normal_matrix(),
+            projector_view: self.projector.camera.view(),
+            projector_projection: self.projector.projection(),
+            light: Unif(vec3(0.0, 0.0, 5.0)),
+            awesome: &self.awesome,
+        };
+        let frustum = api.frustum(&self.viewer, model);
+        let program = self.program.get();
+        {
+            let scope = api.profiler.scope("ruins");
+            let params = scope.params(&api.default_params);
+            let count = try!(self.ruins.draw(api.surface, &params, &program, &uniforms, &frustum));
+            scope.count_draws(count.drawn);
+            scope.count_culled(count.culled);
+        }
+        self.projector.draw(api, self)
+    }
+
+
+    fn process_event(&mut self, event: Event) {
+        self.projector.process_event(event);
+    }
+
+    fn update(&mut self, dt: f32, actions: &Actions) {
+        self.viewer.update(dt, actions);
+        self.projector.update(dt, actions);
+    }
+
+    /// The viewer follows the simulation time, only the projector moves.
+    fn update_camera(&mut self, dt: f32, actions: &Actions) {
+        self.projector.update(dt, actions);
+    }
+
+    fn resize(&mut self, _facade: &GlutinFacade, width: u32, height: u32) -> Result<()> {
+        self.viewer.resize(width, height);
+        self.projector.camera.resize(width, height);
+        Ok(())
+    }
+
+    fn camera(&self) -> Option<&Camera> {
+        Some(&self.viewer)
+    }
+
+    fn camera_mut(&mut self) -> Option<&mut Camera> {
+        Some(&mut self.viewer)
+    }
+}
+
+struct Projector {
+    camera: FlyCamera,
+    frustrum: Frustrum,
+    vertex_buffer: VertexBuffer<vertex::Vertex>,
+    program: WatchedProgram,
+}
+
+impl Projector {
+    fn new(facade: &GlutinFacade) -> Result<Projector> {
+        let vertex_buffer = try!(VertexBuffer::new(facade, &vertex::Vertex::many(models::cube())));
+        let program = try!(load_program(facade, "proj/vertex.glsl", "proj/fragment.glsl"));
+
+        Ok(Projector {
+            camera: FlyCamera::new(vec3(0.0, 2.0, 3.0), vec3(0.0, 0.0, 3.0), Y)
+                        .with_projection(Projection::perspective(deg(30.0))
+                                             .with_aspect_ratio(1.0)
+                                             .with_clip_planes(0.5, 20.0)),
+            frustrum: try!(Frustrum::new(facade)),
+            vertex_buffer: vertex_buffer,
+            program: program,
+        })
+    }
+
+    fn draw<S: Surface>(&self, api: &mut Api<S>, p: &Bacon) -> Result<()> {
+        try!(self.frustrum.draw(api, p));
+        let uniforms = uniform! {
+            model: self.model(),
+            view: p.viewer.view(),
+            projection: api.projection(&p.viewer),
+        };
+        Ok(try!(api.surface.draw(&self.vertex_buffer,
+                                 &NoIndices(PrimitiveType::TrianglesList),
+                                 &self.program.get(),
+                                 &uniforms,
+                                 &api.default_params)))
+    }
+
+    /// The texture is square, whatever the shape of the window.
+    fn projection(&self) -> Mat4 {
+        self.camera.projection().matrix(1.0)
+    }
+
+    fn model(&self) -> Mat4 {
+        id().translate(self.camera.position()).scale(0.25) * self.camera.rotation()
+    }
+
+    fn process_event(&mut self, event: Event) {
+        self.camera.process_event(event);
+    }
+
+    fn update(&mut self, dt: f32, actions: &Actions) {
+        self.camera.update(dt, actions);
+    }
+}
+
+
+struct Frustrum {
+    vertex_buffer: VertexBuffer<vertex::Vertex>,
+    program: WatchedProgram,
+}
+
+impl Frustrum {
+    fn new(facade: &GlutinFacade) -> Result<Frustrum> {
+        let vertex_buffer = try!(VertexBuffer::new(facade, &vertex::Vertex::many(models::cube())));
+        let program = try!(load_program(facade, "frustrum/vertex.glsl", "frustrum/fragment.glsl"));
+
+        Ok(Frustrum {
+            vertex_buffer: vertex_buffer,
+            program: program,
+        })
+    }
+
+    fn draw<S: Surface>(&self, api: &mut Api<S>, p: &Bacon) -> Result<()> {
+        let uniforms = uniform! {
+            model: id(),
+            view: p.viewer.view(),
+            projection: api.projection(&p.viewer),
+            projector_view: p.projector.camera.view(),
+            projector_projection: p.projector.projection(),
+        };
+        Ok(try!(api.surface.draw(&self.vertex_buffer,
+                                 &NoIndices(PrimitiveType::TrianglesList),
+                                 &self.program.get(),
+                                 &uniforms,
+                                 &DrawParameters {
+                                     polygon_mode: PolygonMode::Line,
+                                     ..api.default_params.clone()
+                                 })))
+    }
+}
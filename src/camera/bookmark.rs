@@ -0,0 +1,168 @@
+use std::fs::{self, File};
+use std::io::prelude::*;
+use std::path::{Path, PathBuf};
+
+use cgmath::Deg;
+
+use {Result, Oops, oops};
+use assets::AssetManager;
+use math::{Vec3, vec3};
+use super::{Camera, Projection, Lens, direction, angles};
+
+
+#[derive(Debug, Clone, Copy)]
+pub struct Bookmark {
+    pub position: Vec3,
+    pub yaw: Deg<f32>,
+    pub pitch: Deg<f32>,
+    pub projection: Projection,
+}
+
+impl Bookmark {
+    pub fn from_camera(camera: &Camera) -> Bookmark {
+        // The view looks along -Z, the third row of the rotation.
+        let m = camera.view().0;
+        let (yaw, pitch) = angles(-vec3(m.x.z, m.y.z, m.z.z));
+        Bookmark {
+            position: camera.position(),
+            yaw: yaw,
+            pitch: pitch,
+            projection: camera.projection(),
+        }
+    }
+
+    /// Whether the camera moved, the projection is set either way.
+    pub fn apply(&self, camera: &mut Camera) -> bool {
+        camera.set_projection(self.projection);
+        camera.set_pose(self.position, self.position + direction(self.yaw, self.pitch))
+    }
+}
+
+/// Lines of `slot x y z yaw pitch near far lens fov|height [aspect]`, and `#`
+/// comments.
+#[derive(Debug, Clone)]
+pub struct Bookmarks {
+    slots: Vec<(u32, Bookmark)>,
+}
+
+impl Bookmarks {
+    pub fn new() -> Bookmarks {
+        Bookmarks { slots: Vec::new() }
+    }
+
+    /// Empty if `path` does not exist.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Bookmarks> {
+        let path = path.as_ref();
+        let mut result = Bookmarks::new();
+        if !path.exists() {
+            return Ok(result);
+        }
+        let mut text = String::new();
+        try!(File::open(path)
+                 .and_then(|mut f| f.read_to_string(&mut text))
+                 .map_err(|e| oops(format!("failed to read {}", path.display()), e)));
+        for (i, line) in text.lines().enumerate() {
+            if line.trim().is_empty() || line.trim().starts_with('#') {
+                continue;
+            }
+            let (slot, bookmark) = try!(parse_line(line).ok_or_else(|| {
+                Oops::new(format!("{}:{}: invalid bookmark {:?}, expected `slot x y z yaw pitch \
+                                   near far lens value [aspect]`",
+                                  path.display(),
+                                  i + 1,
+                                  line))
+            }));
+            result.set(slot, bookmark);
+        }
+        Ok(result)
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        let mut text = "# slot  x y z  yaw pitch  near far  lens value  [aspect]\n".to_owned();
+        for &(slot, ref b) in &self.slots {
+            let p = b.projection;
+            let (lens, value) = match p.lens {
+                Lens::Perspective(fov) => ("perspective", fov.s),
+                Lens::Infinite(fov) => ("infinite", fov.s),
+                Lens::ReversedZ(fov) => ("reversed_z", fov.s),
+                Lens::Orthographic(height) => ("orthographic", height),
+            };
+            text.push_str(&format!("{}  {} {} {}  {} {}  {} {}  {} {}",
+                                   slot,
+                                   b.position.x,
+                                   b.position.y,
+                                   b.position.z,
+                                   b.yaw.s,
+                                   b.pitch.s,
+                                   p.near,
+                                   p.far,
+                                   lens,
+                                   value));
+            if let Some(aspect_ratio) = p.aspect_ratio {
+                text.push_str(&format!("  {}", aspect_ratio));
+            }
+            text.push('\n');
+        }
+        if let Some(dir) = path.parent() {
+            try!(fs::create_dir_all(dir)
+                     .map_err(|e| oops(format!("failed to create {}", dir.display()), e)));
+        }
+        File::create(path)
+            .and_then(|mut f| f.write_all(text.as_bytes()))
+            .map_err(|e| oops(format!("failed to write {}", path.display()), e))
+    }
+
+    /// `bookmarks/<scene>.txt` in the assets.
+    pub fn default_path(scene: &str) -> PathBuf {
+        AssetManager::current().locate(format!("bookmarks/{}.txt", scene))
+    }
+
+    pub fn get(&self, slot: u32) -> Option<&Bookmark> {
+        self.slots.iter().find(|&&(s, _)| s == slot).map(|&(_, ref b)| b)
+    }
+
+    pub fn set(&mut self, slot: u32, bookmark: Bookmark) {
+        self.slots.retain(|&(s, _)| s != slot);
+        self.slots.push((slot, bookmark));
+        self.slots.sort_by_key(|&(s, _)| s);
+    }
+}
+
+fn parse_line(line: &str) -> Option<(u32, Bookmark)> {
+    let words = line.split_whitespace().collect::<Vec<_>>();
+    if words.len() != 10 && words.len() != 11 {
+        return None;
+    }
+    let slot = match words[0].parse::<u32>() {
+        Ok(slot) => slot,
+        Err(_) => return None,
+    };
+    let mut numbers = Vec::new();
+    for word in words[1..8].iter().chain(words[9..].iter()) {
+        match word.parse::<f32>() {
+            Ok(n) => numbers.push(n),
+            Err(_) => return None,
+        }
+    }
+    let value = numbers[7];
+    let lens = match words[8] {
+        "perspective" => Lens::Perspective(Deg::new(value)),
+        "infinite" => Lens::Infinite(Deg::new(value)),
+        "reversed_z" => Lens::ReversedZ(Deg::new(value)),
+        "orthographic" => Lens::Orthographic(value),
+        _ => return None,
+    };
+    let bookmark = Bookmark {
+        position: vec3(numbers[0], numbers[1], numbers[2]),
+        yaw: Deg::new(numbers[3]),
+        pitch: Deg::new(numbers[4]),
+        projection: Projection {
+            lens: lens,
+            near: numbers[5],
+            far: numbers[6],
+            aspect_ratio: numbers.get(8).cloned(),
+        },
+    };
+    Some((slot, bookmark))
+}
@@ -0,0 +1,49 @@
+use math::{Vec3, Mat4, look_at};
+use super::{Camera, Projection};
+
+/// Looks from `eye` at `center` and ignores the input.
+pub struct FixedCamera {
+    eye: Vec3,
+    center: Vec3,
+    up: Vec3,
+    projection: Projection,
+}
+
+impl FixedCamera {
+    pub fn new(eye: Vec3, center: Vec3, up: Vec3) -> FixedCamera {
+        FixedCamera {
+            eye: eye,
+            center: center,
+            up: up,
+            projection: Projection::default(),
+        }
+    }
+
+    pub fn with_projection(self, projection: Projection) -> FixedCamera {
+        FixedCamera { projection: projection, ..self }
+    }
+}
+
+impl Camera for FixedCamera {
+    fn view(&self) -> Mat4 {
+        look_at(self.eye, self.center, self.up)
+    }
+
+    fn position(&self) -> Vec3 {
+        self.eye
+    }
+
+    fn projection(&self) -> Projection {
+        self.projection
+    }
+
+    fn set_projection(&mut self, projection: Projection) {
+        self.projection = projection;
+    }
+
+    fn set_pose(&mut self, eye: Vec3, center: Vec3) -> bool {
+        self.eye = eye;
+        self.center = center;
+        true
+    }
+}
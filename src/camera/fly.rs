@@ -0,0 +1,284 @@
+use glium::glutin::Event;
+use cgmath::{Point3, Point, Matrix4, Vector, EuclideanVector, Deg, Quaternion, Rotation3, Rad,
+             Angle};
+
+use math::{Vec3, Mat4, X, Y};
+use input::Actions;
+use super::{Camera, Projection, Mouse, direction, angles, MAX_PITCH};
+
+/// How fast `roll_left` and `roll_right` turn the camera, in degrees per
+/// second.
+const ROLL_RATE: f32 = 90.0;
+
+/// Free flight: looks around with the mouse and accelerates along the held
+/// movement actions. The orientation is yaw around `Y`, then pitch, then roll
+/// around the view direction.
+pub struct FlyCamera {
+    eye: Point3<f32>,
+    up: Vec3,
+
+    /// Top speed in units per second.
+    speed: f32,
+    /// How fast the top speed is reached, in units per second squared.
+    acceleration: f32,
+    /// Exponential decay rate of the velocity, per second.
+    damping: f32,
+    /// Speed multiplier while `sprint` is held.
+    sprint: f32,
+    velocity: Vec3,
+    sensitivity: f32,
+
+    mouse: Mouse,
+    pitch: Deg<f32>,
+    yaw: Deg<f32>,
+    roll: Deg<f32>,
+    projection: Projection,
+}
+
+impl FlyCamera {
+    /// Looks from `eye` at `center`, see `FlyCamera::look_at`. `up` is also
+    /// the direction of the `up` action.
+    pub fn new(eye: Vec3, center: Vec3, up: Vec3) -> FlyCamera {
+        let mut result = FlyCamera {
+            eye: Point3::from_vec(eye),
+            up: up,
+            speed: 8.0,
+            acceleration: 60.0,
+            damping: 6.0,
+            sprint: 3.0,
+            velocity: Vec3::zero(),
+            sensitivity: 20000.0,
+            pitch: Deg::new(0.0),
+            yaw: Deg::new(0.0),
+            roll: Deg::new(0.0),
+            mouse: Mouse::new(),
+            projection: Projection::default(),
+        };
+        result.look_at(eye, center, up);
+        result
+    }
+
+    pub fn with_speed(self, speed: f32) -> FlyCamera {
+        FlyCamera { speed: speed, ..self }
+    }
+
+    pub fn with_acceleration(self, acceleration: f32) -> FlyCamera {
+        FlyCamera { acceleration: acceleration, ..self }
+    }
+
+    pub fn with_damping(self, damping: f32) -> FlyCamera {
+        FlyCamera { damping: damping, ..self }
+    }
+
+    pub fn with_sprint(self, multiplier: f32) -> FlyCamera {
+        FlyCamera { sprint: multiplier, ..self }
+    }
+
+    pub fn with_projection(self, projection: Projection) -> FlyCamera {
+        FlyCamera { projection: projection, ..self }
+    }
+
+    /// Rotates the camera space, where the camera looks along -Z with +Y up,
+    /// into the world space.
+    pub fn rotation(&self) -> Mat4 {
+        Mat4(Matrix4::from(self.quaternion()))
+    }
+
+    /// `(yaw, pitch)` of the view direction.
+    pub fn orientation(&self) -> (Deg<f32>, Deg<f32>) {
+        (self.yaw, self.pitch)
+    }
+
+    pub fn roll(&self) -> Deg<f32> {
+        self.roll
+    }
+
+    /// Places the camera at `eye`, without roll, and stops it.
+    pub fn set_orientation(&mut self, eye: Vec3, yaw: Deg<f32>, pitch: Deg<f32>) {
+        self.eye = Point3::from_vec(eye);
+        self.yaw = yaw;
+        self.pitch = pitch;
+        self.roll = Deg::new(0.0);
+        self.velocity = Vec3::zero();
+    }
+
+    /// Places the camera at `eye` looking at `center` with `up` towards the
+    /// top of the view, like `math::look_at`, and stops it. An `up` tilted
+    /// away from `Y` becomes roll. The pitch is not clamped here, so the
+    /// camera can look straight down.
+    pub fn look_at(&mut self, eye: Vec3, center: Vec3, up: Vec3) {
+        let front = (center - eye).normalize();
+        let (yaw, pitch) = angles(front);
+        self.set_orientation(eye, yaw, pitch);
+        // The top of the view without roll is rotated onto `up` made
+        // perpendicular to the view direction.
+        let up = up - front * up.dot(front);
+        if up.length2() > 1e-12 {
+            let up = up.normalize();
+            let level = self.quaternion();
+            self.roll = Deg::atan2(-up.dot(level * X), up.dot(level * Y));
+        }
+    }
+
+    fn quaternion(&self) -> Quaternion<f32> {
+        // Yaw is measured from +X towards +Z, see `direction`, while the
+        // camera looks along -Z.
+        let yaw = Quaternion::from_angle_y(Rad::from(Deg::new(-90.0 - self.yaw.s)));
+        let pitch = Quaternion::from_angle_x(Rad::from(self.pitch));
+        let roll = Quaternion::from_angle_z(Rad::from(self.roll));
+        yaw * pitch * roll
+    }
+
+    fn right(&self) -> Vec3 {
+        self.quaternion() * X
+    }
+
+    fn front(&self) -> Vec3 {
+        direction(self.yaw, self.pitch)
+    }
+}
+
+impl Camera for FlyCamera {
+    fn view(&self) -> Mat4 {
+        let rotation = Matrix4::from(self.quaternion().conjugate());
+        Mat4(rotation * Matrix4::from_translation(-self.eye.to_vec()))
+    }
+
+    fn position(&self) -> Vec3 {
+        self.eye.to_vec()
+    }
+
+    fn projection(&self) -> Projection {
+        self.projection
+    }
+
+    fn set_projection(&mut self, projection: Projection) {
+        self.projection = projection;
+    }
+
+    /// Records the mouse movement, it is applied on the next `update`.
+    fn process_event(&mut self, event: Event) {
+        self.mouse.process_event(&event)
+    }
+
+    /// Accelerates along the held `forward`, `back`, `left`, `right`, `up` and
+    /// `down` actions and moves with the resulting velocity. `sprint` raises the
+    /// top speed.
+    fn update(&mut self, delta_t: f32, actions: &Actions) {
+        let directions = [("forward", self.front()),
+                          ("back", -self.front()),
+                          ("right", self.right()),
+                          ("left", -self.right()),
+                          ("up", self.up),
+                          ("down", -self.up)];
+        let wish = directions.iter()
+                             .filter(|&&(action, _)| actions.held(action))
+                             .fold(Vec3::zero(), |acc, &(_, direction)| acc + direction);
+        let sprint = if actions.held("sprint") { self.sprint } else { 1.0 };
+        if wish.length2() > 0.0 {
+            self.velocity = self.velocity + wish.normalize() * self.acceleration * sprint * delta_t;
+        }
+        self.velocity = self.velocity * (-self.damping * delta_t).exp();
+        let top_speed = self.speed * sprint;
+        if self.velocity.length() > top_speed {
+            self.velocity = self.velocity.normalize_to(top_speed);
+        }
+        self.eye = self.eye + self.velocity * delta_t;
+
+        let delta = self.mouse.take() * self.sensitivity * delta_t;
+        self.yaw = self.yaw + Deg::new(delta.x);
+        if delta.y != 0.0 {
+            self.pitch = Deg::new((self.pitch.s - delta.y).max(-MAX_PITCH).min(MAX_PITCH));
+        }
+        if actions.held("roll_left") {
+            self.roll = self.roll + Deg::new(ROLL_RATE * delta_t);
+        }
+        if actions.held("roll_right") {
+            self.roll = self.roll - Deg::new(ROLL_RATE * delta_t);
+        }
+    }
+
+    /// Mouse movement is measured relative to the window size.
+    fn resize(&mut self, width: u32, height: u32) {
+        self.mouse.resize(width, height);
+    }
+
+    fn set_pose(&mut self, eye: Vec3, center: Vec3) -> bool {
+        let up = self.up;
+        self.look_at(eye, center, up);
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use glium::glutin::Event;
+    use cgmath::EuclideanVector;
+
+    use math::{self, Vec3, vec3};
+    use math::test_util::assert_mat4_close;
+    use input::{Actions, Bindings};
+    use camera::{Camera, MAX_PITCH};
+    use super::FlyCamera;
+
+    fn check(eye: Vec3, center: Vec3, up: Vec3) {
+        let camera = FlyCamera::new(eye, center, up);
+        assert_mat4_close(camera.view(), math::look_at(eye, center, up));
+    }
+
+    #[test]
+    fn view_matches_look_at() {
+        let eyes = [vec3(0.0, 0.0, 0.0), vec3(1.0, 2.0, 3.0), vec3(-5.0, 0.5, 8.0)];
+        let directions = [vec3(1.0, 0.0, 0.0),
+                          vec3(0.0, 0.0, -1.0),
+                          vec3(-1.0, 0.3, 0.2),
+                          vec3(0.5, -0.8, -0.5),
+                          vec3(-0.2, 0.1, 1.0)];
+        for &eye in &eyes {
+            for &direction in &directions {
+                check(eye, eye + direction * 3.0, vec3(0.0, 1.0, 0.0));
+            }
+        }
+    }
+
+    #[test]
+    fn view_matches_look_at_near_vertical() {
+        let eye = vec3(1.0, 2.0, 3.0);
+        check(eye, eye + vec3(0.01, 1.0, 0.01), vec3(0.0, 1.0, 0.0));
+        check(eye, eye + vec3(-0.01, -1.0, 0.0), vec3(0.0, 1.0, 0.0));
+        check(eye, eye + vec3(0.0, -1.0, 0.0), vec3(0.0, 0.0, 1.0));
+        check(eye, eye + vec3(0.0, 1.0, 0.0), vec3(1.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn view_matches_look_at_with_roll() {
+        let eye = vec3(-2.0, 0.5, 4.0);
+        let ups = [vec3(1.0, 1.0, 0.0),
+                   vec3(-1.0, 0.2, 0.0),
+                   vec3(0.0, -1.0, 0.0),
+                   vec3(0.3, 0.4, 0.9)];
+        for &up in &ups {
+            check(eye, vec3(0.0, 0.0, 0.0), up.normalize());
+            check(eye, eye + vec3(0.2, 0.9, -0.3), up);
+        }
+    }
+
+    #[test]
+    fn pitch_stays_below_vertical() {
+        let mut camera = FlyCamera::new(vec3(0.0, 0.0, 5.0), vec3(0.0, 0.0, 0.0), math::Y);
+        let actions = Actions::new(Bindings::default());
+        camera.resize(800, 600);
+        let mut y = 0;
+        for &step in &[6000, 6000, -12000, -12000] {
+            camera.process_event(Event::MouseMoved((400, y)));
+            y += step;
+            camera.process_event(Event::MouseMoved((400, y)));
+            camera.update(1.0 / 60.0, &actions);
+            let (_, pitch) = camera.orientation();
+            assert!(pitch.s.abs() <= MAX_PITCH && MAX_PITCH < 90.0,
+                    "pitch {:?} is not clamped",
+                    pitch);
+        }
+        assert_eq!(camera.orientation().1.s, MAX_PITCH);
+    }
+}
@@ -0,0 +1,119 @@
+use glium::glutin::Event;
+use cgmath::{Vector, Vector2, EuclideanVector, vec2, vec3, Deg, Angle};
+
+use math::{Vec3, Mat4, Ray};
+use input::Actions;
+
+mod fly;
+mod orbit;
+mod fixed;
+mod scripted;
+mod user;
+mod path;
+mod projection;
+mod bookmark;
+
+pub use self::fly::FlyCamera;
+pub use self::orbit::OrbitCamera;
+pub use self::fixed::FixedCamera;
+pub use self::scripted::ScriptedCamera;
+pub use self::user::UserCamera;
+pub use self::path::{Keyframe, CameraPath, PathCamera, KEYFRAME_SPACING};
+pub use self::projection::{Projection, Lens};
+pub use self::bookmark::{Bookmark, Bookmarks};
+
+type Vec2 = Vector2<f32>;
+
+/// Mouse look stops short of the poles, where yaw is undefined.
+const MAX_PITCH: f32 = 89.0;
+
+
+/// Where the scene is looked at from. Painters forward the events, updates and
+/// resizes to their cameras, the `App` uses `set_pose` for golden shots.
+pub trait Camera {
+    fn view(&self) -> Mat4;
+
+    fn position(&self) -> Vec3;
+
+    fn projection(&self) -> Projection {
+        Projection::default()
+    }
+
+    /// Cameras with a fixed projection ignore it.
+    fn set_projection(&mut self, _projection: Projection) {}
+
+    /// The ray from the camera through `cursor`, in pixels from the top left
+    /// corner of a window of `window_size`.
+    fn ray(&self, cursor: (i32, i32), window_size: (u32, u32)) -> Ray {
+        let (width, height) = (window_size.0 as f32, window_size.1 as f32);
+        let x = 2.0 * cursor.0 as f32 / width - 1.0;
+        let y = 1.0 - 2.0 * cursor.1 as f32 / height;
+        self.projection().unproject(self.view(), width / height, x, y)
+    }
+
+    fn process_event(&mut self, _event: Event) {}
+
+    fn update(&mut self, _dt: f32, _actions: &Actions) {}
+
+    fn resize(&mut self, _width: u32, _height: u32) {}
+
+    /// Moves the camera to `eye`, looking at `center`, and returns whether it
+    /// did. Cameras which are not controlled directly ignore it.
+    fn set_pose(&mut self, _eye: Vec3, _center: Vec3) -> bool {
+        false
+    }
+}
+
+/// The unit vector a camera with `yaw` and `pitch` looks along.
+fn direction(yaw: Deg<f32>, pitch: Deg<f32>) -> Vec3 {
+    vec3(yaw.cos() * pitch.cos(), pitch.sin(), yaw.sin() * pitch.cos())
+}
+
+/// The inverse of `direction`, as `(yaw, pitch)`.
+fn angles(direction: Vec3) -> (Deg<f32>, Deg<f32>) {
+    let direction = direction.normalize();
+    (Deg::atan2(direction.z, direction.x), Deg::asin(direction.y))
+}
+
+/// Mouse movement relative to the window size, accumulated between updates.
+struct Mouse {
+    window_size: (u32, u32),
+    previous_position: Option<Vec2>,
+    pending: Vec2,
+}
+
+impl Mouse {
+    fn new() -> Mouse {
+        Mouse {
+            window_size: (800, 600),
+            previous_position: None,
+            pending: Vec2::zero(),
+        }
+    }
+
+    fn resize(&mut self, width: u32, height: u32) {
+        if width > 0 && height > 0 {
+            self.window_size = (width, height);
+        }
+        self.previous_position = None;
+        self.pending = Vec2::zero();
+    }
+
+    fn process_event(&mut self, event: &Event) {
+        if let Event::MouseMoved((x, y)) = *event {
+            let (width, height) = self.window_size;
+            let position = vec2(x as f32 / width as f32, y as f32 / height as f32);
+            if let Some(prev) = self.previous_position {
+                self.pending = self.pending + (position - prev);
+            }
+            self.previous_position = Some(position);
+        }
+    }
+
+    /// The movement since the last call.
+    fn take(&mut self) -> Vec2 {
+        let result = self.pending;
+        self.pending = Vec2::zero();
+        result
+    }
+}
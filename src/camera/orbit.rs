@@ -0,0 +1,139 @@
+use glium::glutin::Event;
+use cgmath::{EuclideanVector, Deg, Angle};
+
+use math::{Vec3, Mat4, Y, look_at};
+use input::Actions;
+use super::{Camera, Projection, Lens, Mouse, direction, angles, MAX_PITCH};
+
+/// Field of view `OrbitCamera::frame` backs off with for an orthographic lens.
+const FRAMING_FOV: f32 = 45.0;
+
+/// Each wheel step changes the distance by this factor.
+const ZOOM_STEP: f32 = 0.9;
+
+const MIN_DISTANCE: f32 = 0.05;
+
+/// Turns around a target point while `rotate` is held, `zoom_in` and
+/// `zoom_out` change the distance to it.
+pub struct OrbitCamera {
+    target: Vec3,
+    distance: f32,
+    yaw: Deg<f32>,
+    pitch: Deg<f32>,
+    sensitivity: f32,
+    mouse: Mouse,
+    projection: Projection,
+}
+
+impl OrbitCamera {
+    /// Orbits around `target`, looking at it from `eye`.
+    pub fn new(eye: Vec3, target: Vec3) -> OrbitCamera {
+        let mut result = OrbitCamera {
+            target: target,
+            distance: 1.0,
+            yaw: Deg::new(0.0),
+            pitch: Deg::new(0.0),
+            sensitivity: 20000.0,
+            mouse: Mouse::new(),
+            projection: Projection::default(),
+        };
+        result.set_pose(eye, target);
+        result
+    }
+
+    pub fn with_projection(self, projection: Projection) -> OrbitCamera {
+        OrbitCamera { projection: projection, ..self }
+    }
+
+    pub fn target(&self) -> Vec3 {
+        self.target
+    }
+
+    pub fn distance(&self) -> f32 {
+        self.distance
+    }
+
+    /// `(yaw, pitch)` of the view direction.
+    pub fn orientation(&self) -> (Deg<f32>, Deg<f32>) {
+        (self.yaw, self.pitch)
+    }
+
+    /// Looks from `eye` in the given direction. The target is placed in front
+    /// of the camera, at the current distance.
+    pub fn set_orientation(&mut self, eye: Vec3, yaw: Deg<f32>, pitch: Deg<f32>) {
+        self.yaw = yaw;
+        self.pitch = pitch;
+        self.target = eye + self.front() * self.distance;
+    }
+
+    /// Orbits around the center of the box from `min` to `max`, backing off
+    /// along the current view direction until the whole box is visible. An
+    /// orthographic lens is resized to the box instead.
+    pub fn frame(&mut self, min: Vec3, max: Vec3) {
+        let radius = (max - min).length() * 0.5;
+        self.target = (min + max) * 0.5;
+        let fov = self.projection.fov().unwrap_or(Deg::new(FRAMING_FOV));
+        self.distance = (radius / (fov / 2.0).sin()).max(MIN_DISTANCE);
+        if let Lens::Orthographic(_) = self.projection.lens {
+            let (width, height) = self.mouse.window_size;
+            let aspect_ratio = self.projection.aspect_ratio.unwrap_or(width as f32 / height as f32);
+            self.projection.lens = Lens::Orthographic(2.0 * radius * (1.0 / aspect_ratio).max(1.0));
+        }
+    }
+
+    fn front(&self) -> Vec3 {
+        direction(self.yaw, self.pitch)
+    }
+}
+
+impl Camera for OrbitCamera {
+    fn view(&self) -> Mat4 {
+        look_at(self.position(), self.target, Y)
+    }
+
+    fn position(&self) -> Vec3 {
+        self.target - self.front() * self.distance
+    }
+
+    fn projection(&self) -> Projection {
+        self.projection
+    }
+
+    fn set_projection(&mut self, projection: Projection) {
+        self.projection = projection;
+    }
+
+    fn process_event(&mut self, event: Event) {
+        self.mouse.process_event(&event)
+    }
+
+    fn update(&mut self, delta_t: f32, actions: &Actions) {
+        if actions.pressed("zoom_in") {
+            self.distance = (self.distance * ZOOM_STEP).max(MIN_DISTANCE);
+        }
+        if actions.pressed("zoom_out") {
+            self.distance = self.distance / ZOOM_STEP;
+        }
+        let delta = self.mouse.take() * self.sensitivity * delta_t;
+        if actions.held("rotate") {
+            self.yaw = self.yaw + Deg::new(delta.x);
+            self.pitch = Deg::new((self.pitch.s - delta.y).max(-MAX_PITCH).min(MAX_PITCH));
+        }
+    }
+
+    fn resize(&mut self, width: u32, height: u32) {
+        self.mouse.resize(width, height);
+    }
+
+    fn set_pose(&mut self, eye: Vec3, center: Vec3) -> bool {
+        let direction = center - eye;
+        if direction.length2() > 0.0 {
+            let (yaw, pitch) = angles(direction);
+            self.yaw = yaw;
+            self.pitch = pitch;
+            self.distance = direction.length();
+        }
+        self.target = center;
+        true
+    }
+}
@@ -0,0 +1,217 @@
+use std::fs::{self, File};
+use std::io::prelude::*;
+use std::path::{Path, PathBuf};
+
+use cgmath::{Matrix3, Matrix4, Quaternion, Deg};
+
+use {Result, Oops, oops};
+use assets::AssetManager;
+use input::Actions;
+use math::{Vec3, Mat4, vec3, deg};
+use super::{Camera, Projection};
+
+/// Seconds between a recorded keyframe and the previous one.
+pub const KEYFRAME_SPACING: f32 = 2.0;
+
+
+/// `orientation` rotates the camera space, looking along -Z, into the world.
+#[derive(Debug, Clone, Copy)]
+pub struct Keyframe {
+    pub time: f32,
+    pub position: Vec3,
+    pub orientation: Quaternion<f32>,
+    pub fov: Deg<f32>,
+}
+
+impl Keyframe {
+    pub fn from_camera(camera: &Camera, time: f32) -> Keyframe {
+        let m = camera.view().0;
+        let rotation = Matrix3::new(m.x.x, m.x.y, m.x.z, m.y.x, m.y.y, m.y.z, m.z.x, m.z.y, m.z.z);
+        Keyframe {
+            time: time,
+            position: camera.position(),
+            orientation: Quaternion::from(rotation).conjugate(),
+            fov: camera.projection().fov().unwrap_or(deg(45.0)),
+        }
+    }
+
+    pub fn view(&self) -> Mat4 {
+        let rotation = Matrix4::from(self.orientation.conjugate());
+        Mat4(rotation * Matrix4::from_translation(-self.position))
+    }
+}
+
+/// Lines of `time x y z qw qx qy qz fov`, and `#` comments.
+#[derive(Debug, Clone)]
+pub struct CameraPath {
+    keyframes: Vec<Keyframe>,
+}
+
+impl CameraPath {
+    pub fn new() -> CameraPath {
+        CameraPath { keyframes: Vec::new() }
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<CameraPath> {
+        let path = path.as_ref();
+        let mut text = String::new();
+        try!(File::open(path)
+                 .and_then(|mut f| f.read_to_string(&mut text))
+                 .map_err(|e| oops(format!("failed to read {}", path.display()), e)));
+        let mut result = CameraPath::new();
+        for (i, line) in text.lines().enumerate() {
+            if line.trim().is_empty() || line.trim().starts_with('#') {
+                continue;
+            }
+            let numbers = line.split_whitespace().map(|w| w.parse::<f32>()).collect::<Vec<_>>();
+            if numbers.len() != 9 || numbers.iter().any(|n| n.is_err()) {
+                return Err(Oops::new(format!("{}:{}: invalid keyframe {:?}, expected \
+                                              `time x y z qw qx qy qz fov`",
+                                             path.display(),
+                                             i + 1,
+                                             line)));
+            }
+            let n = numbers.into_iter().map(|n| n.unwrap()).collect::<Vec<_>>();
+            result.push(Keyframe {
+                time: n[0],
+                position: vec3(n[1], n[2], n[3]),
+                orientation: Quaternion::new(n[4], n[5], n[6], n[7]).normalize(),
+                fov: Deg::new(n[8]),
+            });
+        }
+        Ok(result)
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        let mut text = "# time  x y z  qw qx qy qz  fov\n".to_owned();
+        for k in &self.keyframes {
+            let q = k.orientation;
+            text.push_str(&format!("{}  {} {} {}  {} {} {} {}  {}\n",
+                                   k.time,
+                                   k.position.x,
+                                   k.position.y,
+                                   k.position.z,
+                                   q.s,
+                                   q.v.x,
+                                   q.v.y,
+                                   q.v.z,
+                                   k.fov.s));
+        }
+        if let Some(dir) = path.parent() {
+            try!(fs::create_dir_all(dir)
+                     .map_err(|e| oops(format!("failed to create {}", dir.display()), e)));
+        }
+        File::create(path)
+            .and_then(|mut f| f.write_all(text.as_bytes()))
+            .map_err(|e| oops(format!("failed to write {}", path.display()), e))
+    }
+
+    /// `paths/<scene>.txt` in the assets.
+    pub fn default_path(scene: &str) -> PathBuf {
+        AssetManager::current().locate(format!("paths/{}.txt", scene))
+    }
+
+    /// Keeps the keyframes ordered by time.
+    pub fn push(&mut self, keyframe: Keyframe) {
+        let i = self.keyframes
+                    .iter()
+                    .position(|k| k.time > keyframe.time)
+                    .unwrap_or(self.keyframes.len());
+        self.keyframes.insert(i, keyframe);
+    }
+
+    pub fn keyframes(&self) -> &[Keyframe] {
+        &self.keyframes
+    }
+
+    pub fn duration(&self) -> f32 {
+        self.keyframes.last().map_or(0.0, |k| k.time)
+    }
+
+    /// A Catmull-Rom spline through the positions and slerp of the orientations.
+    pub fn sample(&self, time: f32) -> Option<Keyframe> {
+        let k = &self.keyframes;
+        let next = match k.iter().position(|k| k.time > time) {
+            Some(0) => return k.first().cloned(),
+            Some(i) => i,
+            None => return k.last().cloned(),
+        };
+        let (i1, i2) = (next - 1, next);
+        let i0 = if i1 == 0 { 0 } else { i1 - 1 };
+        let i3 = if i2 + 1 < k.len() { i2 + 1 } else { i2 };
+        let t = (time - k[i1].time) / (k[i2].time - k[i1].time);
+
+        let (q1, mut q2) = (k[i1].orientation, k[i2].orientation);
+        // Take the shorter way around.
+        if q1.dot(q2) < 0.0 {
+            q2 = -q2;
+        }
+        let (p0, p1, p2, p3) = (k[i0].position, k[i1].position, k[i2].position, k[i3].position);
+        Some(Keyframe {
+            time: time,
+            position: catmull_rom(p0, p1, p2, p3, t),
+            orientation: q1.slerp(q2, t).normalize(),
+            fov: Deg::new(k[i1].fov.s + (k[i2].fov.s - k[i1].fov.s) * t),
+        })
+    }
+}
+
+fn catmull_rom(p0: Vec3, p1: Vec3, p2: Vec3, p3: Vec3, t: f32) -> Vec3 {
+    let (t2, t3) = (t * t, t * t * t);
+    (p1 * 2.0 + (p2 - p0) * t + (p0 * 2.0 - p1 * 5.0 + p2 * 4.0 - p3) * t2 +
+     (p1 * 3.0 - p0 - p2 * 3.0 + p3) * t3) * 0.5
+}
+
+/// Loops a `CameraPath`, with the field of view of the keyframes.
+pub struct PathCamera {
+    path: CameraPath,
+    time: f32,
+    pose: Keyframe,
+    projection: Projection,
+}
+
+impl PathCamera {
+    pub fn new(path: CameraPath) -> Result<PathCamera> {
+        let pose = try!(path.sample(0.0).ok_or_else(|| Oops::new("camera path is empty")));
+        Ok(PathCamera {
+            path: path,
+            time: 0.0,
+            pose: pose,
+            projection: Projection::default(),
+        })
+    }
+
+    pub fn with_projection(self, projection: Projection) -> PathCamera {
+        PathCamera { projection: projection, ..self }
+    }
+}
+
+impl Camera for PathCamera {
+    fn view(&self) -> Mat4 {
+        self.pose.view()
+    }
+
+    fn position(&self) -> Vec3 {
+        self.pose.position
+    }
+
+    fn projection(&self) -> Projection {
+        self.projection.with_fov(self.pose.fov)
+    }
+
+    fn set_projection(&mut self, projection: Projection) {
+        self.projection = projection;
+    }
+
+    fn update(&mut self, dt: f32, _actions: &Actions) {
+        self.time += dt;
+        let duration = self.path.duration();
+        if duration > 0.0 && self.time > duration {
+            self.time = self.time % duration;
+        }
+        if let Some(pose) = self.path.sample(self.time) {
+            self.pose = pose;
+        }
+    }
+}
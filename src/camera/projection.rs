@@ -0,0 +1,154 @@
+use cgmath::{Deg, Angle, SquareMatrix, EuclideanVector, vec4};
+use glium::draw_parameters::DepthTest;
+
+use math::{Mat4, Ray, perspective, infinite_perspective, reversed_perspective, orthographic, deg};
+
+/// The shape of the volume seen by a camera.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Lens {
+    Perspective(Deg<f32>),
+    /// A perspective without a far plane.
+    Infinite(Deg<f32>),
+    /// An infinite perspective with the depth reversed, see
+    /// `math::reversed_perspective`.
+    ReversedZ(Deg<f32>),
+    /// The height of the box seen, the width follows from the aspect ratio.
+    Orthographic(f32),
+}
+
+/// How a camera projects the view space to the clip space.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Projection {
+    pub lens: Lens,
+    pub near: f32,
+    /// Ignored by the infinite lenses.
+    pub far: f32,
+    /// Used instead of the aspect ratio of the target, for cameras which do
+    /// not draw to the window, like a projector.
+    pub aspect_ratio: Option<f32>,
+}
+
+impl Default for Projection {
+    /// 45° perspective from 0.1 to 100.
+    fn default() -> Projection {
+        Projection::perspective(deg(45.0))
+    }
+}
+
+impl Projection {
+    pub fn perspective(fov: Deg<f32>) -> Projection {
+        Projection {
+            lens: Lens::Perspective(fov),
+            near: 0.1,
+            far: 100.0,
+            aspect_ratio: None,
+        }
+    }
+
+    pub fn infinite(fov: Deg<f32>) -> Projection {
+        Projection { lens: Lens::Infinite(fov), ..Projection::default() }
+    }
+
+    pub fn reversed_z(fov: Deg<f32>) -> Projection {
+        Projection { lens: Lens::ReversedZ(fov), ..Projection::default() }
+    }
+
+    pub fn orthographic(height: f32) -> Projection {
+        Projection { lens: Lens::Orthographic(height), ..Projection::default() }
+    }
+
+    pub fn with_clip_planes(self, near: f32, far: f32) -> Projection {
+        Projection {
+            near: near,
+            far: far,
+            ..self
+        }
+    }
+
+    pub fn with_aspect_ratio(self, aspect_ratio: f32) -> Projection {
+        Projection { aspect_ratio: Some(aspect_ratio), ..self }
+    }
+
+    /// Changes the field of view of a perspective lens, orthographic ones are
+    /// kept.
+    pub fn with_fov(self, fov: Deg<f32>) -> Projection {
+        let lens = match self.lens {
+            Lens::Perspective(_) => Lens::Perspective(fov),
+            Lens::Infinite(_) => Lens::Infinite(fov),
+            Lens::ReversedZ(_) => Lens::ReversedZ(fov),
+            Lens::Orthographic(height) => Lens::Orthographic(height),
+        };
+        Projection { lens: lens, ..self }
+    }
+
+    /// The following lens of perspective, infinite, reversed-Z and
+    /// orthographic, in that order. The orthographic box is as high as the
+    /// perspective sees at `distance` and the other way around.
+    pub fn next_lens(&self, distance: f32) -> Projection {
+        let lens = match self.lens {
+            Lens::Perspective(fov) => Lens::Infinite(fov),
+            Lens::Infinite(fov) => Lens::ReversedZ(fov),
+            Lens::ReversedZ(fov) => Lens::Orthographic(2.0 * distance * (fov / 2.0).tan()),
+            Lens::Orthographic(height) => {
+                Lens::Perspective(Deg::atan(height / (2.0 * distance)) * 2.0)
+            }
+        };
+        Projection { lens: lens, ..*self }
+    }
+
+    /// The vertical field of view, `None` for orthographic lenses.
+    pub fn fov(&self) -> Option<Deg<f32>> {
+        match self.lens {
+            Lens::Perspective(fov) | Lens::Infinite(fov) | Lens::ReversedZ(fov) => Some(fov),
+            Lens::Orthographic(_) => None,
+        }
+    }
+
+    /// The projection matrix for a target with `aspect_ratio`, unless the
+    /// projection has its own.
+    pub fn matrix(&self, aspect_ratio: f32) -> Mat4 {
+        let aspect_ratio = self.aspect_ratio.unwrap_or(aspect_ratio);
+        match self.lens {
+            Lens::Perspective(fov) => perspective(fov, aspect_ratio, self.near, self.far),
+            Lens::Infinite(fov) => infinite_perspective(fov, aspect_ratio, self.near),
+            Lens::ReversedZ(fov) => reversed_perspective(fov, aspect_ratio, self.near),
+            Lens::Orthographic(height) => {
+                orthographic(height * aspect_ratio, height, self.near, self.far)
+            }
+        }
+    }
+
+    /// The ray from the near plane through the point `(x, y)` of the target,
+    /// in normalized device coordinates, for a camera with `view`.
+    pub fn unproject(&self, view: Mat4, aspect_ratio: f32, x: f32, y: f32) -> Ray {
+        let inverse = (self.matrix(aspect_ratio) * view).0.invert().expect("singular projection");
+        // The near plane and a depth in front of the far plane, which is at
+        // infinity for some lenses.
+        let (near, beyond) = match self.lens {
+            Lens::ReversedZ(_) => (1.0, 0.5),
+            _ => (-1.0, 0.0),
+        };
+        let point = |z: f32| {
+            let p = inverse * vec4(x, y, z, 1.0);
+            p.truncate() / p.w
+        };
+        let origin = point(near);
+        Ray::new(origin, (point(beyond) - origin).normalize())
+    }
+
+    /// The depth test to draw with, nearer fragments pass it.
+    pub fn depth_test(&self) -> DepthTest {
+        match self.lens {
+            Lens::ReversedZ(_) => DepthTest::IfMore,
+            _ => DepthTest::IfLess,
+        }
+    }
+
+    /// The value to clear the depth buffer with, the farthest depth.
+    pub fn clear_depth(&self) -> f32 {
+        match self.lens {
+            Lens::ReversedZ(_) => 0.0,
+            _ => 1.0,
+        }
+    }
+}
@@ -0,0 +1,47 @@
+use input::Actions;
+use math::{Vec3, Mat4, Y, look_at};
+use super::{Camera, Projection};
+
+/// Follows a function of the simulation time returning `(eye, center)`.
+pub struct ScriptedCamera {
+    script: Box<Fn(f32) -> (Vec3, Vec3)>,
+    time: f32,
+    projection: Projection,
+}
+
+impl ScriptedCamera {
+    pub fn new<F: Fn(f32) -> (Vec3, Vec3) + 'static>(script: F) -> ScriptedCamera {
+        ScriptedCamera {
+            script: Box::new(script),
+            time: 0.0,
+            projection: Projection::default(),
+        }
+    }
+
+    pub fn with_projection(self, projection: Projection) -> ScriptedCamera {
+        ScriptedCamera { projection: projection, ..self }
+    }
+}
+
+impl Camera for ScriptedCamera {
+    fn view(&self) -> Mat4 {
+        let (eye, center) = (self.script)(self.time);
+        look_at(eye, center, Y)
+    }
+
+    fn position(&self) -> Vec3 {
+        (self.script)(self.time).0
+    }
+
+    fn projection(&self) -> Projection {
+        self.projection
+    }
+
+    fn set_projection(&mut self, projection: Projection) {
+        self.projection = projection;
+    }
+
+    fn update(&mut self, dt: f32, _actions: &Actions) {
+        self.time += dt;
+    }
+}
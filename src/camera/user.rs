@@ -0,0 +1,141 @@
+use glium::glutin::Event;
+use cgmath::{EuclideanVector, Deg};
+
+use math::{Vec3, Mat4};
+use input::Actions;
+use super::{Camera, Projection, FlyCamera, OrbitCamera};
+
+/// The camera driven by the user: free flight, or orbiting around the point
+/// in front of it after the `orbit` action. Switching keeps the position and
+/// the view direction, but the orbit is always upright: the roll of the
+/// flight is lost, and the flight resumes level after orbiting. The
+/// `lens` action cycles through the lenses, see `Projection::next_lens`.
+pub struct UserCamera {
+    fly: FlyCamera,
+    orbit: OrbitCamera,
+    orbiting: bool,
+    window_size: (u32, u32),
+}
+
+impl UserCamera {
+    pub fn new(eye: Vec3, center: Vec3, up: Vec3) -> UserCamera {
+        UserCamera {
+            fly: FlyCamera::new(eye, center, up),
+            orbit: OrbitCamera::new(eye, center),
+            orbiting: false,
+            window_size: (800, 600),
+        }
+    }
+
+    /// Changes the settings of the free flight.
+    pub fn with_fly<F: FnOnce(FlyCamera) -> FlyCamera>(self, f: F) -> UserCamera {
+        UserCamera { fly: f(self.fly), ..self }
+    }
+
+    /// Used both in flight and while orbiting.
+    pub fn with_projection(self, projection: Projection) -> UserCamera {
+        UserCamera {
+            fly: self.fly.with_projection(projection),
+            orbit: self.orbit.with_projection(projection),
+            ..self
+        }
+    }
+
+    pub fn is_orbiting(&self) -> bool {
+        self.orbiting
+    }
+
+    /// Switches between flight and orbit, see `UserCamera`.
+    pub fn toggle_orbit(&mut self) {
+        if self.orbiting {
+            let (yaw, pitch) = self.orbit.orientation();
+            self.fly.set_orientation(self.orbit.position(), yaw, pitch);
+        } else {
+            if self.fly.roll() != Deg::new(0.0) {
+                debug!("Orbiting upright, dropping the roll of {:?}", self.fly.roll());
+            }
+            let (yaw, pitch) = self.fly.orientation();
+            self.orbit.set_orientation(self.fly.position(), yaw, pitch);
+        }
+        self.orbiting = !self.orbiting;
+        // Forget the mouse position, the other camera has not seen it move.
+        let (width, height) = self.window_size;
+        self.resize(width, height);
+    }
+
+    /// Changes to the next lens, with the orthographic box as high as the
+    /// view at the orbit distance.
+    pub fn next_lens(&mut self) {
+        let projection = self.projection().next_lens(self.orbit.distance());
+        info!("Switching to the {:?} lens", projection.lens);
+        self.set_projection(projection);
+    }
+
+    /// Switches to orbiting around the box from `min` to `max`, see
+    /// `OrbitCamera::frame`.
+    pub fn frame(&mut self, min: Vec3, max: Vec3) {
+        if !self.orbiting {
+            self.toggle_orbit();
+        }
+        self.orbit.frame(min, max);
+    }
+
+    fn current(&self) -> &Camera {
+        if self.orbiting {
+            &self.orbit
+        } else {
+            &self.fly
+        }
+    }
+
+    fn current_mut(&mut self) -> &mut Camera {
+        if self.orbiting {
+            &mut self.orbit
+        } else {
+            &mut self.fly
+        }
+    }
+}
+
+impl Camera for UserCamera {
+    fn view(&self) -> Mat4 {
+        self.current().view()
+    }
+
+    fn position(&self) -> Vec3 {
+        self.current().position()
+    }
+
+    fn projection(&self) -> Projection {
+        self.current().projection()
+    }
+
+    fn set_projection(&mut self, projection: Projection) {
+        self.fly.set_projection(projection);
+        self.orbit.set_projection(projection);
+    }
+
+    fn process_event(&mut self, event: Event) {
+        self.current_mut().process_event(event)
+    }
+
+    fn update(&mut self, dt: f32, actions: &Actions) {
+        if actions.pressed("orbit") {
+            self.toggle_orbit();
+        }
+        if actions.pressed("lens") {
+            self.next_lens();
+        }
+        self.current_mut().update(dt, actions)
+    }
+
+    fn resize(&mut self, width: u32, height: u32) {
+        self.window_size = (width, height);
+        self.fly.resize(width, height);
+        self.orbit.resize(width, height);
+    }
+
+    fn set_pose(&mut self, eye: Vec3, center: Vec3) -> bool {
+        (center - eye).length2() > 0.0 && self.current_mut().set_pose(eye, center)
+    }
+}
@@ -0,0 +1,108 @@
+use std::fs::File;
+use std::io::{self, BufWriter};
+use std::io::prelude::*;
+use std::path::{Path, PathBuf};
+
+use {Result, Oops, Snapshot, oops};
+
+
+/// Where `--record` puts the frames.
+#[derive(Debug, Clone)]
+pub enum RecordOutput {
+    /// `frame_0000.png`, `frame_0001.png`, ... in a directory.
+    Png(PathBuf),
+    /// A single raw YUV4MPEG2 stream, which ffmpeg and most players understand.
+    Y4m(PathBuf),
+}
+
+/// Records every frame at a fixed virtual frame rate, independent of how long
+/// it takes to render a frame.
+#[derive(Debug, Clone)]
+pub struct Record {
+    pub output: RecordOutput,
+    pub fps: u32,
+}
+
+pub struct Recorder {
+    record: Record,
+    frame: u32,
+    /// The stream and the frame size in its header.
+    y4m: Option<(BufWriter<File>, (u32, u32))>,
+}
+
+impl Recorder {
+    pub fn new(record: Record) -> Recorder {
+        info!("Recording to {:?} at {} fps", record.output, record.fps);
+        Recorder {
+            record: record,
+            frame: 0,
+            y4m: None,
+        }
+    }
+
+    /// Fails if the frames of a y4m stream change size, which the format does
+    /// not allow.
+    pub fn write(&mut self, snapshot: &Snapshot) -> Result<()> {
+        match self.record.output.clone() {
+            RecordOutput::Png(dir) => {
+                try!(snapshot.save(dir.join(format!("frame_{:04}.png", self.frame))));
+            }
+            RecordOutput::Y4m(path) => {
+                if self.y4m.is_none() {
+                    let out = try!(create_y4m(&path, snapshot, self.record.fps));
+                    self.y4m = Some((out, (snapshot.width, snapshot.height)));
+                }
+                let (ref mut out, (width, height)) = *self.y4m.as_mut().unwrap();
+                if (snapshot.width, snapshot.height) != (width, height) {
+                    return Err(Oops::new(format!("the window was resized to {}x{} while \
+                                                  recording {}, which is {}x{}",
+                                                 snapshot.width,
+                                                 snapshot.height,
+                                                 path.display(),
+                                                 width,
+                                                 height)));
+                }
+                try!(write_y4m_frame(out, snapshot)
+                         .map_err(|e| oops(format!("failed to write {}", path.display()), e)));
+            }
+        }
+        self.frame += 1;
+        Ok(())
+    }
+}
+
+/// Picks the first free `screenshots/<name>_000.png`.
+pub fn screenshot_path(name: &str) -> PathBuf {
+    let dir = Path::new("./screenshots");
+    (0..)
+        .map(|i| dir.join(format!("{}_{:03}.png", name, i)))
+        .find(|p| !p.exists())
+        .unwrap()
+}
+
+fn create_y4m(path: &Path, snapshot: &Snapshot, fps: u32) -> Result<BufWriter<File>> {
+    let file = try!(File::create(path)
+                        .map_err(|e| oops(format!("failed to create {}", path.display()), e)));
+    let mut out = BufWriter::new(file);
+    try!(write!(out,
+                "YUV4MPEG2 W{} H{} F{}:1 Ip A1:1 C444\n",
+                snapshot.width,
+                snapshot.height,
+                fps)
+             .map_err(|e| oops(format!("failed to write {}", path.display()), e)));
+    Ok(out)
+}
+
+/// Writes a frame as full resolution Y, Cb and Cr planes (BT.601, studio range).
+fn write_y4m_frame<W: Write>(out: &mut W, snapshot: &Snapshot) -> io::Result<()> {
+    let n = (snapshot.width * snapshot.height) as usize;
+    let mut planes = vec![0u8; 3 * n];
+    for (i, p) in snapshot.pixels.chunks(4).enumerate() {
+        let (r, g, b) = (p[0] as f32, p[1] as f32, p[2] as f32);
+        planes[i] = (16.0 + 0.257 * r + 0.504 * g + 0.098 * b).round() as u8;
+        planes[n + i] = (128.0 - 0.148 * r - 0.291 * g + 0.439 * b).round() as u8;
+        planes[2 * n + i] = (128.0 + 0.439 * r - 0.368 * g - 0.071 * b).round() as u8;
+    }
+    try!(out.write_all(b"FRAME\n"));
+    out.write_all(&planes)
+}
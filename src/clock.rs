@@ -0,0 +1,191 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use time;
+
+use app::STEP;
+
+
+pub trait Clock {
+    /// Seconds, never decreasing.
+    fn now(&mut self) -> f64;
+}
+
+pub struct RealTimeClock {
+    start: f64,
+}
+
+impl RealTimeClock {
+    pub fn new() -> RealTimeClock {
+        RealTimeClock { start: time::precise_time_s() }
+    }
+}
+
+impl Clock for RealTimeClock {
+    fn now(&mut self) -> f64 {
+        time::precise_time_s() - self.start
+    }
+}
+
+/// Advances by `step` every time it is read.
+pub struct FixedStepClock {
+    step: f64,
+    frames: u64,
+}
+
+impl FixedStepClock {
+    pub fn new(step: f64) -> FixedStepClock {
+        FixedStepClock {
+            step: step,
+            frames: 0,
+        }
+    }
+}
+
+impl Clock for FixedStepClock {
+    fn now(&mut self) -> f64 {
+        let result = self.frames as f64 * self.step;
+        self.frames += 1;
+        result
+    }
+}
+
+/// Only changes when it is set, clones share it.
+#[derive(Clone)]
+pub struct ManualClock {
+    time: Rc<Cell<f64>>,
+}
+
+impl ManualClock {
+    pub fn new() -> ManualClock {
+        ManualClock { time: Rc::new(Cell::new(0.0)) }
+    }
+
+    pub fn set(&self, time: f64) {
+        self.time.set(time);
+    }
+
+    pub fn advance(&self, delta: f64) {
+        self.time.set(self.time.get() + delta);
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&mut self) -> f64 {
+        self.time.get()
+    }
+}
+
+/// Pause, slow motion and single steps.
+#[derive(Debug)]
+pub struct TimeControl {
+    pub paused: bool,
+    pub scale: f32,
+    pending_steps: u32,
+}
+
+impl TimeControl {
+    pub fn new() -> TimeControl {
+        TimeControl {
+            paused: false,
+            scale: 1.0,
+            pending_steps: 0,
+        }
+    }
+
+    pub fn toggle_pause(&mut self) {
+        self.paused = !self.paused;
+        self.pending_steps = 0;
+        info!("{}", if self.paused { "Paused" } else { "Resumed" });
+    }
+
+    pub fn set_scale(&mut self, scale: f32) {
+        self.scale = scale.max(1.0 / 64.0).min(64.0);
+        info!("Time scale {}", self.scale);
+    }
+
+    pub fn single_step(&mut self) {
+        if self.paused {
+            self.pending_steps += 1;
+        }
+    }
+
+    /// The part of a frame duration the cameras run on alone, while paused.
+    pub fn camera_time(&self, clock_delta: f32) -> f32 {
+        if self.paused { clock_delta } else { 0.0 }
+    }
+
+    pub fn simulation_time(&mut self, clock_delta: f32) -> f32 {
+        if self.paused {
+            let steps = self.pending_steps;
+            self.pending_steps = 0;
+            steps as f32 * STEP
+        } else {
+            clock_delta * self.scale
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use app::STEP;
+    use super::{Clock, FixedStepClock, ManualClock, TimeControl};
+
+    #[test]
+    fn running_time_is_scaled() {
+        let mut control = TimeControl::new();
+        assert_eq!(control.simulation_time(0.1), 0.1);
+        assert_eq!(control.camera_time(0.1), 0.0);
+        control.set_scale(0.5);
+        assert_eq!(control.simulation_time(0.1), 0.05);
+        control.set_scale(1000.0);
+        assert_eq!(control.scale, 64.0);
+        control.set_scale(0.0);
+        assert_eq!(control.scale, 1.0 / 64.0);
+    }
+
+    #[test]
+    fn paused_time_goes_to_the_cameras() {
+        let mut control = TimeControl::new();
+        control.toggle_pause();
+        assert_eq!(control.simulation_time(0.1), 0.0);
+        assert_eq!(control.camera_time(0.1), 0.1);
+        control.toggle_pause();
+        assert_eq!(control.simulation_time(0.1), 0.1);
+    }
+
+    #[test]
+    fn single_steps_only_while_paused() {
+        let mut control = TimeControl::new();
+        control.single_step();
+        assert_eq!(control.simulation_time(0.0), 0.0);
+        control.toggle_pause();
+        control.single_step();
+        control.single_step();
+        assert_eq!(control.simulation_time(0.5), 2.0 * STEP);
+        assert_eq!(control.simulation_time(0.5), 0.0);
+        // Left over steps are dropped when resuming.
+        control.single_step();
+        control.toggle_pause();
+        control.toggle_pause();
+        assert_eq!(control.simulation_time(0.0), 0.0);
+    }
+
+    #[test]
+    fn fixed_step_clock() {
+        let mut clock = FixedStepClock::new(0.25);
+        assert_eq!(clock.now(), 0.0);
+        assert_eq!(clock.now(), 0.25);
+        assert_eq!(clock.now(), 0.5);
+    }
+
+    #[test]
+    fn manual_clock_clones_share_the_time() {
+        let clock = ManualClock::new();
+        let mut app_clock = clock.clone();
+        assert_eq!(app_clock.now(), 0.0);
+        clock.set(2.0);
+        clock.advance(0.5);
+        assert_eq!(app_clock.now(), 2.5);
+    }
+}
@@ -0,0 +1,204 @@
+use std::path::PathBuf;
+
+use glium::glutin::{self, WindowBuilder, HeadlessRendererBuilder, GlRequest, GlProfile};
+
+use Result;
+use args::Args;
+use capture::{Record, RecordOutput};
+
+
+/// Window and OpenGL context settings passed to `App::run`.
+#[derive(Debug, Clone)]
+pub struct AppConfig {
+    pub title: String,
+    pub width: u32,
+    pub height: u32,
+    pub fullscreen: bool,
+    /// Number of MSAA samples, `0` disables multisampling.
+    pub multisampling: u16,
+    pub vsync: bool,
+    /// Requested OpenGL version, the driver's default if `None`.
+    pub gl_version: Option<(u8, u8)>,
+    pub debug: bool,
+    pub depth_bits: u8,
+    pub stencil_bits: u8,
+    /// Show the profiler statistics in the window title and in the log.
+    pub profile: bool,
+    /// Write a Chrome trace of the profiler scopes here on exit.
+    pub trace: Option<PathBuf>,
+    /// Save every frame instead of running in real time.
+    pub record: Option<Record>,
+    /// Write the input events and frame timings to this file.
+    pub record_input: Option<PathBuf>,
+    /// Play back a file written with `record_input` instead of the real input.
+    pub replay: Option<PathBuf>,
+    /// Overrides for the default key bindings, see `Bindings`.
+    pub bindings: Option<PathBuf>,
+    /// Where the `keyframe` action records the camera poses, instead of
+    /// `CameraPath::default_path`.
+    pub camera_path: Option<PathBuf>,
+}
+
+impl AppConfig {
+    pub fn new<S: Into<String>>(title: S) -> AppConfig {
+        AppConfig {
+            title: title.into(),
+            width: 800,
+            height: 600,
+            fullscreen: false,
+            multisampling: 4,
+            vsync: true,
+            gl_version: None,
+            debug: false,
+            depth_bits: 24,
+            stencil_bits: 0,
+            profile: false,
+            trace: None,
+            record: None,
+            record_input: None,
+            replay: None,
+            bindings: None,
+            camera_path: None,
+        }
+    }
+
+    pub fn with_size(self, width: u32, height: u32) -> AppConfig {
+        AppConfig { width: width, height: height, ..self }
+    }
+
+    pub fn with_title<S: Into<String>>(self, title: S) -> AppConfig {
+        AppConfig { title: title.into(), ..self }
+    }
+
+    pub fn with_fullscreen(self, fullscreen: bool) -> AppConfig {
+        AppConfig { fullscreen: fullscreen, ..self }
+    }
+
+    pub fn with_multisampling(self, samples: u16) -> AppConfig {
+        AppConfig { multisampling: samples, ..self }
+    }
+
+    pub fn with_vsync(self, vsync: bool) -> AppConfig {
+        AppConfig { vsync: vsync, ..self }
+    }
+
+    pub fn with_gl_version(self, major: u8, minor: u8) -> AppConfig {
+        AppConfig { gl_version: Some((major, minor)), ..self }
+    }
+
+    pub fn with_debug(self, debug: bool) -> AppConfig {
+        AppConfig { debug: debug, ..self }
+    }
+
+    pub fn with_stencil_bits(self, bits: u8) -> AppConfig {
+        AppConfig { stencil_bits: bits, ..self }
+    }
+
+    pub fn with_profile(self, profile: bool) -> AppConfig {
+        AppConfig { profile: profile, ..self }
+    }
+
+    /// Overrides the settings with the command line flags:
+    ///
+    /// `--size WxH`, `--title T`, `--fullscreen`, `--msaa N`, `--no-vsync`,
+    /// `--gl MAJOR.MINOR`, `--debug-context`, `--stencil N`, `--profile`, `--trace FILE`,
+    /// `--record DIR|FILE.y4m`, `--fps N`, `--record-input FILE`, `--replay FILE`,
+    /// `--bindings FILE`, `--camera-path FILE`.
+    pub fn from_args(self) -> Result<AppConfig> {
+        let args = Args::from_env();
+        let mut result = self;
+        if let Some((width, height)) = try!(args.size("--size")) {
+            result.width = width;
+            result.height = height;
+        }
+        if let Some(title) = args.value("--title") {
+            result.title = title.to_owned();
+        }
+        if args.has("--fullscreen") {
+            result.fullscreen = true;
+        }
+        if let Some(samples) = try!(args.parse("--msaa")) {
+            result.multisampling = samples;
+        }
+        if args.has("--no-vsync") {
+            result.vsync = false;
+        }
+        if let Some(version) = try!(args.version("--gl")) {
+            result.gl_version = Some(version);
+        }
+        if args.has("--debug-context") {
+            result.debug = true;
+        }
+        if let Some(bits) = try!(args.parse("--stencil")) {
+            result.stencil_bits = bits;
+        }
+        if args.has("--profile") {
+            result.profile = true;
+        }
+        if let Some(trace) = args.value("--trace") {
+            result.trace = Some(PathBuf::from(trace));
+        }
+        if let Some(path) = args.value("--record") {
+            let path = PathBuf::from(path);
+            let output = if path.extension().map_or(false, |e| e == "y4m") {
+                RecordOutput::Y4m(path)
+            } else {
+                RecordOutput::Png(path)
+            };
+            result.record = Some(Record {
+                output: output,
+                fps: try!(args.parse("--fps")).unwrap_or(30),
+            });
+        }
+        if let Some(path) = args.value("--record-input") {
+            result.record_input = Some(PathBuf::from(path));
+        }
+        if let Some(path) = args.value("--replay") {
+            result.replay = Some(PathBuf::from(path));
+        }
+        if let Some(path) = args.value("--bindings") {
+            result.bindings = Some(PathBuf::from(path));
+        }
+        if let Some(path) = args.value("--camera-path") {
+            result.camera_path = Some(PathBuf::from(path));
+        }
+        Ok(result)
+    }
+
+    pub fn window_builder(&self) -> WindowBuilder<'static> {
+        let mut builder = WindowBuilder::new()
+                              .with_title(self.title.clone())
+                              .with_dimensions(self.width, self.height)
+                              .with_depth_buffer(self.depth_bits)
+                              .with_gl_profile(GlProfile::Core)
+                              .with_gl_debug_flag(self.debug);
+        if self.stencil_bits > 0 {
+            builder = builder.with_stencil_buffer(self.stencil_bits);
+        }
+        if self.multisampling > 0 {
+            builder = builder.with_multisampling(self.multisampling);
+        }
+        if self.vsync {
+            builder = builder.with_vsync();
+        }
+        if let Some((major, minor)) = self.gl_version {
+            builder = builder.with_gl(GlRequest::Specific(glutin::Api::OpenGl, (major, minor)));
+        }
+        if self.fullscreen {
+            builder = builder.with_fullscreen(glutin::get_primary_monitor());
+        }
+        builder
+    }
+
+    /// The settings of `window_builder` which apply without a window. The
+    /// builder cannot multisample, `App` does it in its offscreen targets.
+    pub fn headless_builder(&self) -> HeadlessRendererBuilder<'static> {
+        let mut builder = HeadlessRendererBuilder::new(self.width, self.height)
+                              .with_gl_profile(GlProfile::Core)
+                              .with_gl_debug_flag(self.debug);
+        if let Some((major, minor)) = self.gl_version {
+            builder = builder.with_gl(GlRequest::Specific(glutin::Api::OpenGl, (major, minor)));
+        }
+        builder
+    }
+}
@@ -0,0 +1,217 @@
+use std::f64;
+use std::path::PathBuf;
+
+use args::Args;
+use assets::AssetManager;
+use math::Vec3;
+use snapshot::Snapshot;
+
+
+/// A golden-image frame, optionally seen from a `(eye, center)` camera pose.
+#[derive(Debug, Clone)]
+pub struct Shot {
+    pub name: String,
+    pub time: f32,
+    pub camera: Option<(Vec3, Vec3)>,
+}
+
+impl Shot {
+    pub fn new<S: Into<String>>(name: S, time: f32) -> Shot {
+        Shot {
+            name: name.into(),
+            time: time,
+            camera: None,
+        }
+    }
+
+    pub fn looking(self, eye: Vec3, center: Vec3) -> Shot {
+        Shot { camera: Some((eye, center)), ..self }
+    }
+}
+
+/// Compares frames with `golden/<scene>/<shot>.png` in the assets, failures
+/// are written to `output`.
+#[derive(Debug, Clone)]
+pub struct Golden {
+    /// Rewrite the reference images instead of comparing against them.
+    pub bless: bool,
+    /// Record the missing reference images and compare the others.
+    pub bless_missing: bool,
+    pub output: PathBuf,
+    /// A pixel mismatches if any channel differs by more than this.
+    pub tolerance: u8,
+    /// Fraction of pixels allowed to mismatch.
+    pub max_mismatched: f64,
+    pub min_psnr: f64,
+    pub min_ssim: f64,
+}
+
+impl Golden {
+    pub fn new() -> Golden {
+        Golden {
+            bless: false,
+            bless_missing: false,
+            output: PathBuf::from("./target/golden"),
+            tolerance: 8,
+            max_mismatched: 0.001,
+            min_psnr: 35.0,
+            min_ssim: 0.98,
+        }
+    }
+
+    /// `Some` if `--golden`, `--bless` or `--bless-missing` is on the command line.
+    pub fn from_args() -> Option<Golden> {
+        let args = Args::from_env();
+        let bless = args.has("--bless");
+        let bless_missing = args.has("--bless-missing");
+        if !bless && !bless_missing && !args.has("--golden") {
+            return None;
+        }
+        Some(Golden {
+            bless: bless,
+            bless_missing: bless_missing,
+            ..Golden::new()
+        })
+    }
+
+    pub fn reference_path(&self, scene: &str, shot: &Shot) -> PathBuf {
+        AssetManager::current().locate(format!("golden/{}/{}.png", scene, shot.name))
+    }
+
+    pub fn actual_path(&self, scene: &str, shot: &Shot) -> PathBuf {
+        self.output.join(scene).join(format!("{}.actual.png", shot.name))
+    }
+
+    pub fn diff_path(&self, scene: &str, shot: &Shot) -> PathBuf {
+        self.output.join(scene).join(format!("{}.diff.png", shot.name))
+    }
+
+    pub fn compare(&self, actual: &Snapshot, expected: &Snapshot) -> Comparison {
+        if actual.width != expected.width || actual.height != expected.height {
+            return Comparison {
+                mismatched: (actual.width * actual.height) as usize,
+                total: (actual.width * actual.height) as usize,
+                psnr: 0.0,
+                ssim: 0.0,
+                diff: actual.clone(),
+            };
+        }
+        let mut mismatched = 0;
+        let mut diff = Vec::with_capacity(actual.pixels.len());
+        for (a, e) in actual.pixels.chunks(4).zip(expected.pixels.chunks(4)) {
+            let delta = a.iter().zip(e).map(|(&a, &e)| abs_diff(a, e)).max().unwrap_or(0);
+            if delta > self.tolerance {
+                mismatched += 1;
+                diff.extend(&[255, 0, 0, 255]);
+            } else {
+                let gray = luma(e) as u8 / 4;
+                diff.extend(&[gray, gray, gray, 255]);
+            }
+        }
+
+        Comparison {
+            mismatched: mismatched,
+            total: (actual.width * actual.height) as usize,
+            psnr: psnr(actual, expected),
+            ssim: ssim(actual, expected),
+            diff: Snapshot {
+                width: actual.width,
+                height: actual.height,
+                pixels: diff,
+            },
+        }
+    }
+
+    pub fn passes(&self, comparison: &Comparison) -> bool {
+        comparison.mismatched_fraction() <= self.max_mismatched &&
+        comparison.psnr >= self.min_psnr && comparison.ssim >= self.min_ssim
+    }
+}
+
+/// `diff` shows the mismatched pixels in red over the darkened reference.
+#[derive(Debug)]
+pub struct Comparison {
+    pub mismatched: usize,
+    pub total: usize,
+    pub psnr: f64,
+    pub ssim: f64,
+    pub diff: Snapshot,
+}
+
+impl Comparison {
+    pub fn mismatched_fraction(&self) -> f64 {
+        self.mismatched as f64 / self.total as f64
+    }
+}
+
+fn abs_diff(a: u8, b: u8) -> u8 {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
+
+fn luma(p: &[u8]) -> f64 {
+    0.299 * p[0] as f64 + 0.587 * p[1] as f64 + 0.114 * p[2] as f64
+}
+
+/// Peak signal-to-noise ratio over the RGB channels, in decibels.
+fn psnr(a: &Snapshot, b: &Snapshot) -> f64 {
+    let mut sum = 0.0;
+    let mut n = 0;
+    for (pa, pb) in a.pixels.chunks(4).zip(b.pixels.chunks(4)) {
+        for c in 0..3 {
+            let d = pa[c] as f64 - pb[c] as f64;
+            sum += d * d;
+            n += 1;
+        }
+    }
+    if sum == 0.0 {
+        return f64::INFINITY;
+    }
+    let mse = sum / n as f64;
+    10.0 * (255.0 * 255.0 / mse).log10()
+}
+
+/// Mean structural similarity of the luminance over 8x8 windows.
+fn ssim(a: &Snapshot, b: &Snapshot) -> f64 {
+    const WINDOW: u32 = 8;
+    let c1 = (0.01f64 * 255.0).powi(2);
+    let c2 = (0.03f64 * 255.0).powi(2);
+
+    let mut total = 0.0;
+    let mut windows = 0;
+    let mut y = 0;
+    while y + WINDOW <= a.height {
+        let mut x = 0;
+        while x + WINDOW <= a.width {
+            let (mut sa, mut sb, mut saa, mut sbb, mut sab) = (0.0, 0.0, 0.0, 0.0, 0.0);
+            for dy in 0..WINDOW {
+                for dx in 0..WINDOW {
+                    let la = luma(&a.pixel(x + dx, y + dy));
+                    let lb = luma(&b.pixel(x + dx, y + dy));
+                    sa += la;
+                    sb += lb;
+                    saa += la * la;
+                    sbb += lb * lb;
+                    sab += la * lb;
+                }
+            }
+            let n = (WINDOW * WINDOW) as f64;
+            let (ma, mb) = (sa / n, sb / n);
+            let va = saa / n - ma * ma;
+            let vb = sbb / n - mb * mb;
+            let cov = sab / n - ma * mb;
+            total += ((2.0 * ma * mb + c1) * (2.0 * cov + c2)) /
+                     ((ma * ma + mb * mb + c1) * (va + vb + c2));
+            windows += 1;
+            x += WINDOW;
+        }
+        y += WINDOW;
+    }
+    if windows == 0 {
+        return 1.0;
+    }
+    total / windows as f64
+}
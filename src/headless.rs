@@ -0,0 +1,60 @@
+use std::path::PathBuf;
+
+use Result;
+use args::Args;
+
+
+/// Renders frame `i` at `time + i * step` to `output/frame_000i.png`, without a window.
+#[derive(Debug, Clone)]
+pub struct Headless {
+    pub output: PathBuf,
+    pub width: u32,
+    pub height: u32,
+    pub frames: u32,
+    pub time: f32,
+    pub step: f32,
+}
+
+impl Headless {
+    pub fn new<P: Into<PathBuf>>(output: P) -> Headless {
+        Headless {
+            output: output.into(),
+            width: 800,
+            height: 600,
+            frames: 1,
+            time: 0.0,
+            step: 1.0 / 60.0,
+        }
+    }
+
+    /// `None` without `--headless DIR`.
+    pub fn from_args() -> Result<Option<Headless>> {
+        let args = Args::from_env();
+        let mut result = match args.value("--headless") {
+            Some(dir) => Headless::new(dir),
+            None => return Ok(None),
+        };
+        if let Some(frames) = try!(args.parse("--frames")) {
+            result.frames = frames;
+        }
+        if let Some(time) = try!(args.parse("--time")) {
+            result.time = time;
+        }
+        if let Some(step) = try!(args.parse("--step")) {
+            result.step = step;
+        }
+        if let Some((width, height)) = try!(args.size("--size")) {
+            result.width = width;
+            result.height = height;
+        }
+        Ok(Some(result))
+    }
+
+    pub fn frame_time(&self, frame: u32) -> f32 {
+        self.time + frame as f32 * self.step
+    }
+
+    pub fn frame_path(&self, frame: u32) -> PathBuf {
+        self.output.join(format!("frame_{:04}.png", frame))
+    }
+}
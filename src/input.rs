@@ -0,0 +1,288 @@
+use std::fs::File;
+use std::io::prelude::*;
+use std::path::Path;
+
+use glium::glutin::{Event, ElementState, MouseButton, MouseScrollDelta, VirtualKeyCode};
+
+use {Result, Oops, oops};
+use replay::key_code;
+
+
+/// Used for the actions which a bindings file does not mention.
+const DEFAULT_BINDINGS: &'static str = "
+quit        Escape
+screenshot  F12
+pause       P
+step        Period
+slower      LBracket
+faster      RBracket
+scene_1     Key1
+scene_2     Key2
+scene_3     Key3
+scene_4     Key4
+scene_5     Key5
+scene_6     Key6
+scene_7     Key7
+scene_8     Key8
+scene_9     Key9
+forward     W
+back        S
+left        A
+right       D
+up          E Space
+down        Q LControl
+sprint      LShift
+roll_left   Z
+roll_right  C
+orbit       O
+lens        L
+rotate      mouse:left
+zoom_in     wheel:up
+zoom_out    wheel:down
+frame       F
+pick        mouse:right
+keyframe    K
+bookmark_1  F1
+bookmark_2  F2
+bookmark_3  F3
+bookmark_4  F4
+save_bookmark_1  F5
+save_bookmark_2  F6
+save_bookmark_3  F7
+save_bookmark_4  F8
+";
+
+/// Something which can be bound to an action.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Input {
+    Key(VirtualKeyCode),
+    Mouse(MouseButton),
+    WheelUp,
+    WheelDown,
+}
+
+impl Input {
+    /// Parses a key name like `W` or `LShift`, `mouse:left|right|middle|N`
+    /// or `wheel:up|down`.
+    pub fn parse(name: &str) -> Option<Input> {
+        let input = match name {
+            "mouse:left" => Input::Mouse(MouseButton::Left),
+            "mouse:right" => Input::Mouse(MouseButton::Right),
+            "mouse:middle" => Input::Mouse(MouseButton::Middle),
+            "wheel:up" => Input::WheelUp,
+            "wheel:down" => Input::WheelDown,
+            _ if name.starts_with("mouse:") => {
+                match name["mouse:".len()..].parse() {
+                    Ok(n) => Input::Mouse(MouseButton::Other(n)),
+                    Err(_) => return None,
+                }
+            }
+            _ => Input::Key(match key_code(name) {
+                Some(code) => code,
+                None => return None,
+            }),
+        };
+        Some(input)
+    }
+}
+
+/// Maps inputs to named actions.
+///
+/// A bindings file has one action per line, followed by the inputs which
+/// trigger it, for example `forward Z Up`. Lines starting with `#` are
+/// comments. An action listed in the file loses its default inputs.
+#[derive(Debug, Clone)]
+pub struct Bindings {
+    bindings: Vec<(Input, String)>,
+}
+
+impl Default for Bindings {
+    fn default() -> Bindings {
+        let mut result = Bindings { bindings: Vec::new() };
+        result.merge("default bindings", DEFAULT_BINDINGS)
+              .expect("invalid default bindings");
+        result
+    }
+}
+
+impl Bindings {
+    /// The default bindings, overridden by the ones from `path`.
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Bindings> {
+        let path = path.as_ref();
+        let mut text = String::new();
+        try!(File::open(path)
+                 .and_then(|mut f| f.read_to_string(&mut text))
+                 .map_err(|e| oops(format!("failed to read {}", path.display()), e)));
+        let mut result = Bindings::default();
+        try!(result.merge(&path.display().to_string(), &text));
+        Ok(result)
+    }
+
+    fn merge(&mut self, source: &str, text: &str) -> Result<()> {
+        for (i, line) in text.lines().enumerate() {
+            let words = line.split_whitespace().collect::<Vec<_>>();
+            if words.is_empty() || words[0].starts_with('#') {
+                continue;
+            }
+            let action = words[0];
+            self.bindings.retain(|&(_, ref a)| a != action);
+            for name in &words[1..] {
+                let input = try!(Input::parse(name).ok_or_else(|| {
+                    Oops::new(format!("{}:{}: unknown input {}", source, i + 1, name))
+                }));
+                self.bindings.push((input, action.to_owned()));
+            }
+        }
+        Ok(())
+    }
+
+    pub fn actions(&self, input: Input) -> Vec<&str> {
+        self.bindings
+            .iter()
+            .filter(|&&(i, _)| i == input)
+            .map(|&(_, ref action)| action.as_ref())
+            .collect()
+    }
+
+    /// Whether `event` starts `action`.
+    pub fn triggers(&self, event: &Event, action: &str) -> bool {
+        match input_of(event) {
+            Some((input, ElementState::Pressed)) => self.actions(input).contains(&action),
+            _ => false,
+        }
+    }
+}
+
+/// The state of the actions, updated from the window events.
+///
+/// An action is held while any of its inputs is held. `pressed` and
+/// `released` are reset after each simulation step, so that every transition is
+/// seen by exactly one `Painter::update`. Wheel actions are pressed and released
+/// at once and are never held.
+pub struct Actions {
+    bindings: Bindings,
+    held: Vec<Input>,
+    pressed: Vec<String>,
+    released: Vec<String>,
+    cursor: Option<(i32, i32)>,
+}
+
+impl Actions {
+    pub fn new(bindings: Bindings) -> Actions {
+        Actions {
+            bindings: bindings,
+            held: Vec::new(),
+            pressed: Vec::new(),
+            released: Vec::new(),
+            cursor: None,
+        }
+    }
+
+    pub fn bindings(&self) -> &Bindings {
+        &self.bindings
+    }
+
+    /// Updates the state and returns the actions started by `event`.
+    pub fn process_event(&mut self, event: &Event) -> Vec<String> {
+        if let Event::Focused(false) = *event {
+            // Releases are not reported to an unfocused window.
+            while let Some(&input) = self.held.last() {
+                self.release(input);
+            }
+            return Vec::new();
+        }
+        if let Event::MouseMoved(position) = *event {
+            self.cursor = Some(position);
+        }
+        match input_of(event) {
+            Some((input, ElementState::Pressed)) => self.press(input),
+            Some((input, ElementState::Released)) => {
+                self.release(input);
+                Vec::new()
+            }
+            None => Vec::new(),
+        }
+    }
+
+    fn press(&mut self, input: Input) -> Vec<String> {
+        // Key repeat sends more presses while the key is held.
+        if self.held.contains(&input) {
+            return Vec::new();
+        }
+        let started = self.bindings
+                          .actions(input)
+                          .into_iter()
+                          .filter(|a| !self.held(a))
+                          .map(|a| a.to_owned())
+                          .collect::<Vec<_>>();
+        self.pressed.extend(started.iter().cloned());
+        if input == Input::WheelUp || input == Input::WheelDown {
+            self.released.extend(started.iter().cloned());
+        } else {
+            self.held.push(input);
+        }
+        started
+    }
+
+    fn release(&mut self, input: Input) {
+        match self.held.iter().position(|&h| h == input) {
+            Some(i) => self.held.remove(i),
+            None => return,
+        };
+        let stopped = self.bindings
+                          .actions(input)
+                          .into_iter()
+                          .filter(|a| !self.held(a))
+                          .map(|a| a.to_owned())
+                          .collect::<Vec<_>>();
+        self.released.extend(stopped);
+    }
+
+    /// Forgets the transitions, called after each simulation step.
+    pub fn end_step(&mut self) {
+        self.pressed.clear();
+        self.released.clear();
+    }
+
+    /// Whether `action` started since the last step.
+    pub fn pressed(&self, action: &str) -> bool {
+        self.pressed.iter().any(|a| a == action)
+    }
+
+    /// Whether `action` is active now.
+    pub fn held(&self, action: &str) -> bool {
+        self.held.iter().any(|&input| self.bindings.actions(input).contains(&action))
+    }
+
+    /// Whether `action` stopped since the last step.
+    pub fn released(&self, action: &str) -> bool {
+        self.released.iter().any(|a| a == action)
+    }
+
+    /// The last position of the mouse, in pixels from the top left corner of
+    /// the window. `None` until the mouse moves over the window.
+    pub fn cursor(&self) -> Option<(i32, i32)> {
+        self.cursor
+    }
+}
+
+fn input_of(event: &Event) -> Option<(Input, ElementState)> {
+    match *event {
+        Event::KeyboardInput(state, _, Some(code)) => Some((Input::Key(code), state)),
+        Event::MouseInput(state, button) => Some((Input::Mouse(button), state)),
+        Event::MouseWheel(delta, ..) => {
+            let y = match delta {
+                MouseScrollDelta::LineDelta(_, y) => y,
+                MouseScrollDelta::PixelDelta(_, y) => y,
+            };
+            if y > 0.0 {
+                Some((Input::WheelUp, ElementState::Pressed))
+            } else if y < 0.0 {
+                Some((Input::WheelDown, ElementState::Pressed))
+            } else {
+                None
+            }
+        }
+        _ => None,
+    }
+}
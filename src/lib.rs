@@ -7,7 +7,6 @@ extern crate glium;
 extern crate quick_error;
 #[macro_use]
 extern crate itertools;
-extern crate gl;
 extern crate cgmath;
 extern crate time;
 extern crate image;
@@ -15,16 +14,39 @@ extern crate tobj;
 
 
 mod result;
+mod args;
+mod config;
 mod app;
+mod headless;
+mod snapshot;
+mod capture;
+mod replay;
+mod input;
+mod clock;
+mod golden;
 mod painter;
+mod scene;
+mod profiler;
 mod assets;
 mod camera;
 mod model;
 pub mod math;
 
 pub use result::{Oops, oops, Result};
-pub use app::App;
+pub use app::{App, STEP};
+pub use config::AppConfig;
+pub use headless::Headless;
+pub use snapshot::Snapshot;
+pub use capture::{Record, RecordOutput};
+pub use replay::{InputFrame, InputRecorder, InputReplay};
+pub use input::{Input, Bindings, Actions};
+pub use clock::{Clock, RealTimeClock, FixedStepClock, ManualClock, TimeControl};
+pub use golden::{Golden, Shot, Comparison};
 pub use painter::{Painter, Api};
-pub use assets::{load_program, load_cubemap, load_texture};
-pub use model::Model;
-pub use camera::Camera;
+pub use scene::{Scene, Registry};
+pub use profiler::{Profiler, Scope, Average};
+pub use assets::{AssetManager, ASSETS_VAR, WatchedProgram, load_program, reload_programs,
+                 load_cubemap, load_texture};
+pub use model::{Model, DrawCount, Hit};
+pub use camera::{Camera, FlyCamera, OrbitCamera, FixedCamera, ScriptedCamera, UserCamera,
+                 Keyframe, CameraPath, PathCamera, Projection, Lens, Bookmark, Bookmarks};
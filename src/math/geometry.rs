@@ -0,0 +1,581 @@
+use std::f32;
+
+use cgmath::{Matrix, Vector, Vector4, EuclideanVector};
+
+use super::{Vec3, Mat4, vec3};
+
+
+/// An axis aligned box from `min` to `max`. It is empty when `min` is greater
+/// than `max` on some axis, like `Aabb::empty()`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Aabb {
+    pub min: Vec3,
+    pub max: Vec3,
+}
+
+impl Aabb {
+    pub fn new(min: Vec3, max: Vec3) -> Aabb {
+        Aabb { min: min, max: max }
+    }
+
+    /// The box containing nothing, which grows to the first point added.
+    pub fn empty() -> Aabb {
+        Aabb::new(vec3(f32::INFINITY, f32::INFINITY, f32::INFINITY),
+                  vec3(f32::NEG_INFINITY, f32::NEG_INFINITY, f32::NEG_INFINITY))
+    }
+
+    pub fn from_points<I: IntoIterator<Item = Vec3>>(points: I) -> Aabb {
+        points.into_iter().fold(Aabb::empty(), |aabb, p| aabb.grow(p))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.min.x > self.max.x || self.min.y > self.max.y || self.min.z > self.max.z
+    }
+
+    /// The box extended to contain `point`.
+    pub fn grow(&self, point: Vec3) -> Aabb {
+        Aabb::new(vec3(self.min.x.min(point.x),
+                       self.min.y.min(point.y),
+                       self.min.z.min(point.z)),
+                  vec3(self.max.x.max(point.x),
+                       self.max.y.max(point.y),
+                       self.max.z.max(point.z)))
+    }
+
+    /// The smallest box containing both boxes.
+    pub fn union(&self, other: &Aabb) -> Aabb {
+        if other.is_empty() {
+            *self
+        } else {
+            self.grow(other.min).grow(other.max)
+        }
+    }
+
+    pub fn center(&self) -> Vec3 {
+        (self.min + self.max) / 2.0
+    }
+
+    pub fn size(&self) -> Vec3 {
+        self.max - self.min
+    }
+
+    pub fn corners(&self) -> [Vec3; 8] {
+        let (a, b) = (self.min, self.max);
+        [vec3(a.x, a.y, a.z),
+         vec3(b.x, a.y, a.z),
+         vec3(a.x, b.y, a.z),
+         vec3(b.x, b.y, a.z),
+         vec3(a.x, a.y, b.z),
+         vec3(b.x, a.y, b.z),
+         vec3(a.x, b.y, b.z),
+         vec3(b.x, b.y, b.z)]
+    }
+
+    /// The point of the box nearest to `point`.
+    pub fn closest_point(&self, point: Vec3) -> Vec3 {
+        vec3(point.x.max(self.min.x).min(self.max.x),
+             point.y.max(self.min.y).min(self.max.y),
+             point.z.max(self.min.z).min(self.max.z))
+    }
+
+    pub fn contains_point(&self, point: Vec3) -> bool {
+        self.min.x <= point.x && point.x <= self.max.x && self.min.y <= point.y &&
+        point.y <= self.max.y && self.min.z <= point.z && point.z <= self.max.z
+    }
+
+    /// Whether `other` is entirely inside the box.
+    pub fn contains(&self, other: &Aabb) -> bool {
+        other.is_empty() || (self.contains_point(other.min) && self.contains_point(other.max))
+    }
+
+    /// Whether the boxes overlap, touching counts.
+    pub fn intersects(&self, other: &Aabb) -> bool {
+        self.min.x <= other.max.x && other.min.x <= self.max.x && self.min.y <= other.max.y &&
+        other.min.y <= self.max.y && self.min.z <= other.max.z && other.min.z <= self.max.z
+    }
+
+    /// The axis aligned box around the box transformed by `matrix`, which is
+    /// larger than the box itself when `matrix` rotates.
+    pub fn transform(&self, matrix: &Mat4) -> Aabb {
+        if self.is_empty() {
+            return *self;
+        }
+        Aabb::from_points(self.corners().iter().map(|&p| {
+            let p = matrix.0 * p.extend(1.0);
+            p.truncate() / p.w
+        }))
+    }
+}
+
+/// A ball around `center`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sphere {
+    pub center: Vec3,
+    pub radius: f32,
+}
+
+impl Sphere {
+    pub fn new(center: Vec3, radius: f32) -> Sphere {
+        Sphere {
+            center: center,
+            radius: radius,
+        }
+    }
+
+    /// The sphere through the corners of `aabb`.
+    pub fn from_aabb(aabb: &Aabb) -> Sphere {
+        Sphere::new(aabb.center(), aabb.size().length() / 2.0)
+    }
+
+    pub fn contains_point(&self, point: Vec3) -> bool {
+        (point - self.center).length2() <= self.radius * self.radius
+    }
+
+    /// Whether the spheres overlap, touching counts.
+    pub fn intersects(&self, other: &Sphere) -> bool {
+        let radius = self.radius + other.radius;
+        (other.center - self.center).length2() <= radius * radius
+    }
+
+    pub fn intersects_aabb(&self, aabb: &Aabb) -> bool {
+        self.contains_point(aabb.closest_point(self.center))
+    }
+
+    /// The sphere transformed by the affine `matrix`. The radius is scaled by
+    /// the largest scale of `matrix`, so the result contains the ellipsoid a
+    /// non-uniform scale gives.
+    pub fn transform(&self, matrix: &Mat4) -> Sphere {
+        let m = matrix.0;
+        let (x, y, z) = (m.x.truncate(), m.y.truncate(), m.z.truncate());
+        let scale = x.length().max(y.length()).max(z.length());
+        Sphere::new((m * self.center.extend(1.0)).truncate(), self.radius * scale)
+    }
+}
+
+/// The points `p` with `normal.dot(p) + distance == 0`. The normal points to
+/// the positive side, and is of unit length for the distances to be exact.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Plane {
+    pub normal: Vec3,
+    pub distance: f32,
+}
+
+impl Plane {
+    pub fn new(normal: Vec3, distance: f32) -> Plane {
+        Plane {
+            normal: normal,
+            distance: distance,
+        }
+    }
+
+    pub fn from_point_normal(point: Vec3, normal: Vec3) -> Plane {
+        let normal = normal.normalize();
+        Plane::new(normal, -normal.dot(point))
+    }
+
+    /// The plane of the triangle `a`, `b`, `c`, with the positive side where
+    /// the triangle is counterclockwise.
+    pub fn from_points(a: Vec3, b: Vec3, c: Vec3) -> Plane {
+        Plane::from_point_normal(a, (b - a).cross(c - a))
+    }
+
+    /// The plane `(a, b, c, d)` with `a x + b y + c z + d == 0`, normalized.
+    /// Without a normal, like the far plane of an infinite perspective, all
+    /// the points are on the side of the sign of `d`.
+    pub fn from_vector(v: Vector4<f32>) -> Plane {
+        let length = v.truncate().length();
+        if length == 0.0 {
+            return Plane::new(v.truncate(), v.w);
+        }
+        Plane::new(v.truncate() / length, v.w / length)
+    }
+
+    /// The signed distance from the plane, positive on the side of the
+    /// normal.
+    pub fn distance_to(&self, point: Vec3) -> f32 {
+        self.normal.dot(point) + self.distance
+    }
+
+    /// The plane transformed by `matrix`, `None` if it is singular.
+    pub fn transform(&self, matrix: &Mat4) -> Option<Plane> {
+        matrix.inverse().map(|inverse| {
+            Plane::from_vector(inverse.0.transpose() * self.normal.extend(self.distance))
+        })
+    }
+}
+
+/// The volume seen through a projection, bounded by six planes with the
+/// inside on their positive side.
+#[derive(Debug, Clone, Copy)]
+pub struct Frustum {
+    pub planes: [Plane; 6],
+}
+
+impl Frustum {
+    /// The planes of `projection * view * model`, in the space of the model.
+    pub fn from_matrix(matrix: &Mat4) -> Frustum {
+        let m = matrix.0;
+        let (x, y, z, w) = (m.row(0), m.row(1), m.row(2), m.row(3));
+        let plane = Plane::from_vector;
+        Frustum {
+            planes: [plane(w + x), plane(w - x), plane(w + y), plane(w - y), plane(w + z),
+                     plane(w - z)],
+        }
+    }
+
+    pub fn contains_point(&self, point: Vec3) -> bool {
+        self.planes.iter().all(|p| p.distance_to(point) >= 0.0)
+    }
+
+    /// Whether some of `aabb` may be inside. Boxes close to an edge of the
+    /// frustum are sometimes kept although they are outside.
+    pub fn intersects_aabb(&self, aabb: &Aabb) -> bool {
+        let (min, max) = (aabb.min, aabb.max);
+        self.planes.iter().all(|p| {
+            // The corner farthest on the inside of the plane.
+            let x = if p.normal.x >= 0.0 { max.x } else { min.x };
+            let y = if p.normal.y >= 0.0 { max.y } else { min.y };
+            let z = if p.normal.z >= 0.0 { max.z } else { min.z };
+            p.distance_to(vec3(x, y, z)) >= 0.0
+        })
+    }
+
+    /// Whether some of `sphere` may be inside, with the same approximation as
+    /// `intersects_aabb`.
+    pub fn intersects_sphere(&self, sphere: &Sphere) -> bool {
+        self.planes.iter().all(|p| p.distance_to(sphere.center) >= -sphere.radius)
+    }
+
+    /// The frustum transformed by `matrix`, `None` if it is singular.
+    pub fn transform(&self, matrix: &Mat4) -> Option<Frustum> {
+        let mut planes = self.planes;
+        for plane in &mut planes {
+            *plane = match plane.transform(matrix) {
+                Some(plane) => plane,
+                None => return None,
+            };
+        }
+        Some(Frustum { planes: planes })
+    }
+}
+
+/// A half line, `origin + direction * t` for `t >= 0`. The direction is not
+/// necessarily of unit length.
+#[derive(Debug, Clone, Copy)]
+pub struct Ray {
+    pub origin: Vec3,
+    pub direction: Vec3,
+}
+
+impl Ray {
+    pub fn new(origin: Vec3, direction: Vec3) -> Ray {
+        Ray {
+            origin: origin,
+            direction: direction,
+        }
+    }
+
+    pub fn at(&self, t: f32) -> Vec3 {
+        self.origin + self.direction * t
+    }
+
+    /// The ray in the space `matrix` maps to. The direction is not normalized,
+    /// so `t` stays the same for the transformed points.
+    pub fn transform(&self, matrix: &Mat4) -> Ray {
+        let origin = matrix.0 * self.origin.extend(1.0);
+        let direction = matrix.0 * self.direction.extend(0.0);
+        Ray::new(origin.truncate() / origin.w, direction.truncate())
+    }
+
+    /// The range of `t` inside `aabb`, if the ray hits it.
+    pub fn intersect_aabb(&self, aabb: &Aabb) -> Option<(f32, f32)> {
+        let (mut near, mut far) = (0.0f32, f32::INFINITY);
+        for axis in 0..3 {
+            if self.direction[axis] == 0.0 {
+                // Parallel to the faces of this axis, the ray stays inside the
+                // slab between them or never enters it.
+                if self.origin[axis] < aabb.min[axis] || self.origin[axis] > aabb.max[axis] {
+                    return None;
+                }
+                continue;
+            }
+            let inverse = 1.0 / self.direction[axis];
+            let t0 = (aabb.min[axis] - self.origin[axis]) * inverse;
+            let t1 = (aabb.max[axis] - self.origin[axis]) * inverse;
+            near = near.max(t0.min(t1));
+            far = far.min(t0.max(t1));
+        }
+        if near <= far { Some((near, far)) } else { None }
+    }
+
+    /// The range of `t` inside `sphere`, if the ray hits it.
+    pub fn intersect_sphere(&self, sphere: &Sphere) -> Option<(f32, f32)> {
+        let offset = self.origin - sphere.center;
+        let a = self.direction.length2();
+        let b = offset.dot(self.direction);
+        let c = offset.length2() - sphere.radius * sphere.radius;
+        let discriminant = b * b - a * c;
+        if discriminant < 0.0 || a == 0.0 {
+            return None;
+        }
+        let root = discriminant.sqrt();
+        let (near, far) = ((-b - root) / a, (-b + root) / a);
+        if far < 0.0 { None } else { Some((near.max(0.0), far)) }
+    }
+
+    /// The `t` where the ray crosses `plane`, if it does.
+    pub fn intersect_plane(&self, plane: &Plane) -> Option<f32> {
+        let speed = plane.normal.dot(self.direction);
+        if speed == 0.0 {
+            return None;
+        }
+        let t = -plane.distance_to(self.origin) / speed;
+        if t >= 0.0 { Some(t) } else { None }
+    }
+
+    /// The `t` where the ray hits the triangle `a`, `b`, `c` from either
+    /// side, using the Möller-Trumbore algorithm.
+    pub fn intersect_triangle(&self, a: Vec3, b: Vec3, c: Vec3) -> Option<f32> {
+        let (ab, ac) = (b - a, c - a);
+        let p = self.direction.cross(ac);
+        let determinant = ab.dot(p);
+        if determinant.abs() < 1e-12 {
+            return None;
+        }
+        let inverse = 1.0 / determinant;
+        let s = self.origin - a;
+        let u = s.dot(p) * inverse;
+        if u < 0.0 || u > 1.0 {
+            return None;
+        }
+        let q = s.cross(ab);
+        let v = self.direction.dot(q) * inverse;
+        if v < 0.0 || u + v > 1.0 {
+            return None;
+        }
+        let t = ac.dot(q) * inverse;
+        if t >= 0.0 { Some(t) } else { None }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f32;
+
+    use cgmath::{Matrix4, EuclideanVector};
+
+    use math::{Vec3, Mat4, id, vec3, perspective, deg, X, Y, Z};
+    use math::test_util::{EPSILON, assert_vec3_close, triangle, unit_box};
+    use super::{Aabb, Sphere, Plane, Frustum, Ray};
+
+    #[test]
+    fn ray_hits_triangle() {
+        let (a, b, c) = triangle();
+        let ray = Ray::new(vec3(0.2, 0.2, 1.0), -Z);
+        assert_eq!(ray.intersect_triangle(a, b, c), Some(1.0));
+        // From the back too.
+        let ray = Ray::new(vec3(0.2, 0.2, -2.0), Z);
+        assert_eq!(ray.intersect_triangle(a, b, c), Some(2.0));
+    }
+
+    #[test]
+    fn ray_misses_triangle() {
+        let (a, b, c) = triangle();
+        let ray = Ray::new(vec3(0.8, 0.8, 1.0), -Z);
+        assert_eq!(ray.intersect_triangle(a, b, c), None);
+        let ray = Ray::new(vec3(-0.1, 0.5, 1.0), -Z);
+        assert_eq!(ray.intersect_triangle(a, b, c), None);
+    }
+
+    #[test]
+    fn ray_hits_triangle_edge() {
+        let (a, b, c) = triangle();
+        let ray = Ray::new(vec3(0.5, 0.0, 1.0), -Z);
+        assert_eq!(ray.intersect_triangle(a, b, c), Some(1.0));
+        let ray = Ray::new(vec3(0.5, 0.5, 1.0), -Z);
+        let t = ray.intersect_triangle(a, b, c).expect("the hypotenuse is hit");
+        assert!((t - 1.0).abs() < EPSILON);
+    }
+
+    #[test]
+    fn ray_parallel_to_triangle() {
+        let (a, b, c) = triangle();
+        assert_eq!(Ray::new(vec3(-1.0, 0.2, 0.0), X).intersect_triangle(a, b, c), None);
+        assert_eq!(Ray::new(vec3(-1.0, 0.2, 1.0), X).intersect_triangle(a, b, c), None);
+    }
+
+    #[test]
+    fn triangle_behind_ray() {
+        let (a, b, c) = triangle();
+        let ray = Ray::new(vec3(0.2, 0.2, -1.0), -Z);
+        assert_eq!(ray.intersect_triangle(a, b, c), None);
+    }
+
+    #[test]
+    fn ray_from_inside_box() {
+        let ray = Ray::new(vec3(0.0, 0.0, 0.0), X);
+        assert_eq!(ray.intersect_aabb(&unit_box()), Some((0.0, 1.0)));
+        let ray = Ray::new(vec3(0.5, 0.0, 0.0), vec3(-1.0, -1.0, 0.0));
+        assert_eq!(ray.intersect_aabb(&unit_box()), Some((0.0, 1.0)));
+    }
+
+    #[test]
+    fn ray_through_box() {
+        let ray = Ray::new(vec3(-3.0, 0.5, 0.0), X);
+        assert_eq!(ray.intersect_aabb(&unit_box()), Some((2.0, 4.0)));
+    }
+
+    #[test]
+    fn ray_in_box_face() {
+        // The direction has zero components, along the top face and along an
+        // edge.
+        let ray = Ray::new(vec3(-3.0, 1.0, 0.0), X);
+        assert_eq!(ray.intersect_aabb(&unit_box()), Some((2.0, 4.0)));
+        let ray = Ray::new(vec3(-3.0, 1.0, -1.0), X);
+        assert_eq!(ray.intersect_aabb(&unit_box()), Some((2.0, 4.0)));
+        let ray = Ray::new(vec3(-3.0, 1.5, 0.0), X);
+        assert_eq!(ray.intersect_aabb(&unit_box()), None);
+    }
+
+    #[test]
+    fn ray_misses_box() {
+        assert_eq!(Ray::new(vec3(-3.0, 0.0, 0.0), -X).intersect_aabb(&unit_box()), None);
+        assert_eq!(Ray::new(vec3(-3.0, 3.0, 0.0), vec3(1.0, 0.1, 0.0)).intersect_aabb(&unit_box()),
+                   None);
+        assert_eq!(Ray::new(vec3(0.0, 0.0, 5.0), Y).intersect_aabb(&unit_box()), None);
+    }
+
+    #[test]
+    fn ray_hits_sphere_and_plane() {
+        let ray = Ray::new(vec3(0.0, 0.0, 5.0), -Z);
+        assert_eq!(ray.intersect_sphere(&Sphere::new(vec3(0.0, 0.0, 0.0), 1.0)), Some((4.0, 6.0)));
+        assert_eq!(ray.intersect_sphere(&Sphere::new(vec3(3.0, 0.0, 0.0), 1.0)), None);
+        assert_eq!(ray.intersect_plane(&Plane::new(Z, 1.0)), Some(6.0));
+        assert_eq!(ray.intersect_plane(&Plane::new(X, 1.0)), None);
+    }
+
+    /// 90° field of view, from 1 to 10, looking along -Z from the origin.
+    fn frustum() -> Frustum {
+        Frustum::from_matrix(&perspective(deg(90.0), 1.0, 1.0, 10.0))
+    }
+
+    #[test]
+    fn frustum_planes() {
+        let frustum = frustum();
+        assert!(frustum.contains_point(vec3(0.0, 0.0, -5.0)));
+        assert!(frustum.contains_point(vec3(4.9, -4.9, -5.0)));
+        assert!(!frustum.contains_point(vec3(5.1, 0.0, -5.0)));
+        assert!(!frustum.contains_point(vec3(0.0, 0.0, -0.5)));
+        assert!(!frustum.contains_point(vec3(0.0, 0.0, -10.5)));
+        assert!(!frustum.contains_point(vec3(0.0, 0.0, 5.0)));
+        for plane in &frustum.planes {
+            assert!((plane.normal.length2() - 1.0).abs() < EPSILON);
+        }
+    }
+
+    #[test]
+    fn frustum_boxes() {
+        let frustum = frustum();
+        let half = vec3(0.5, 0.5, 0.5);
+        let at = |center: Vec3| Aabb::new(center - half, center + half);
+        assert!(frustum.intersects_aabb(&at(vec3(0.0, 0.0, -5.0))));
+        // Partly inside, across the near plane and a side plane.
+        assert!(frustum.intersects_aabb(&at(vec3(0.0, 0.0, -1.0))));
+        assert!(frustum.intersects_aabb(&at(vec3(5.3, 0.0, -5.0))));
+        assert!(!frustum.intersects_aabb(&at(vec3(0.0, 0.0, 5.0))));
+        assert!(!frustum.intersects_aabb(&at(vec3(0.0, 0.0, -20.0))));
+        assert!(!frustum.intersects_aabb(&at(vec3(-8.0, 0.0, -5.0))));
+        assert!(!frustum.intersects_aabb(&at(vec3(0.0, 8.0, -5.0))));
+        // Around the whole frustum.
+        assert!(frustum.intersects_aabb(&Aabb::new(vec3(-50.0, -50.0, -50.0),
+                                                   vec3(50.0, 50.0, 50.0))));
+    }
+
+    #[test]
+    fn frustum_spheres() {
+        let frustum = frustum();
+        assert!(frustum.intersects_sphere(&Sphere::new(vec3(0.0, 0.0, -5.0), 1.0)));
+        // 2 / sqrt(2) from the right plane.
+        assert!(!frustum.intersects_sphere(&Sphere::new(vec3(7.0, 0.0, -5.0), 1.0)));
+        assert!(frustum.intersects_sphere(&Sphere::new(vec3(7.0, 0.0, -5.0), 1.5)));
+        assert!(!frustum.intersects_sphere(&Sphere::new(vec3(0.0, 0.0, 2.0), 1.0)));
+        assert!(frustum.intersects_sphere(&Sphere::new(vec3(0.0, 0.0, -11.0), 1.5)));
+    }
+
+    #[test]
+    fn spheres_touching_and_apart() {
+        let sphere = Sphere::new(vec3(0.0, 0.0, 0.0), 1.0);
+        assert!(sphere.intersects(&Sphere::new(vec3(2.0, 0.0, 0.0), 1.0)));
+        assert!(sphere.intersects(&Sphere::new(vec3(0.0, 1.0, 0.0), 0.5)));
+        assert!(!sphere.intersects(&Sphere::new(vec3(2.1, 0.0, 0.0), 1.0)));
+        assert!(!sphere.intersects(&Sphere::new(vec3(0.0, -3.0, 0.0), 1.5)));
+    }
+
+    #[test]
+    fn sphere_and_box() {
+        let sphere = Sphere::new(vec3(2.0, 0.0, 0.0), 1.0);
+        assert!(sphere.intersects_aabb(&unit_box()));
+        assert!(!Sphere::new(vec3(2.0, 2.0, 0.0), 1.0).intersects_aabb(&unit_box()));
+        let transformed = sphere.transform(&id().translate(vec3(0.0, 1.0, 0.0)).scale(2.0));
+        assert_vec3_close(transformed.center, vec3(4.0, 1.0, 0.0));
+        assert_eq!(transformed.radius, 2.0);
+    }
+
+    #[test]
+    fn plane_transform() {
+        let plane = Plane::new(Y, 0.0);
+        let moved = plane.transform(&id().translate(vec3(3.0, 2.0, 1.0))).unwrap();
+        assert_vec3_close(moved.normal, Y);
+        assert!((moved.distance + 2.0).abs() < EPSILON);
+        assert!(moved.distance_to(vec3(5.0, 3.0, -4.0)) > 0.0);
+
+        let turned = plane.transform(&id().rotate(Z, deg(90.0))).unwrap();
+        assert_vec3_close(turned.normal, -X);
+        assert!(turned.distance.abs() < EPSILON);
+
+        // A non-uniform scale keeps the plane through the scaled points.
+        let tilted = Plane::from_points(X, Y, Z);
+        let scale = Mat4(Matrix4::from_nonuniform_scale(2.0, 3.0, 4.0));
+        let scaled = tilted.transform(&scale).unwrap();
+        assert!((scaled.normal.length2() - 1.0).abs() < EPSILON);
+        for &point in &[vec3(2.0, 0.0, 0.0), vec3(0.0, 3.0, 0.0), vec3(0.0, 0.0, 4.0)] {
+            assert!(scaled.distance_to(point).abs() < EPSILON);
+        }
+        assert!(scaled.distance_to(vec3(0.0, 0.0, 0.0)) < 0.0);
+
+        assert_eq!(plane.transform(&id().scale(0.0)), None);
+    }
+
+    #[test]
+    fn box_union_with_empty() {
+        let empty = Aabb::empty();
+        assert!(empty.is_empty());
+        assert!(!unit_box().is_empty());
+        assert_eq!(unit_box().union(&empty), unit_box());
+        assert_eq!(empty.union(&unit_box()), unit_box());
+        assert!(empty.union(&empty).is_empty());
+        let other = Aabb::new(vec3(0.0, 2.0, -3.0), vec3(4.0, 3.0, 0.0));
+        assert_eq!(unit_box().union(&other),
+                   Aabb::new(vec3(-1.0, -1.0, -3.0), vec3(4.0, 3.0, 1.0)));
+        assert!(unit_box().contains(&empty));
+        assert!(!unit_box().contains(&other));
+    }
+
+    #[test]
+    fn box_transform() {
+        let empty = Aabb::empty();
+        assert!(empty.transform(&id().translate(X)).is_empty());
+
+        let cube = Aabb::new(vec3(0.0, 0.0, 0.0), vec3(1.0, 1.0, 1.0));
+        let moved = cube.transform(&id().translate(X).scale(2.0));
+        assert_vec3_close(moved.min, vec3(1.0, 0.0, 0.0));
+        assert_vec3_close(moved.max, vec3(3.0, 2.0, 2.0));
+
+        // Rotating the unit box by 45° around Y widens it to the diagonal.
+        let turned = unit_box().transform(&id().rotate(Y, deg(45.0)));
+        let half = f32::consts::SQRT_2;
+        assert_vec3_close(turned.min, vec3(-half, -1.0, -half));
+        assert_vec3_close(turned.max, vec3(half, 1.0, half));
+        assert!(turned.contains(&unit_box()));
+    }
+}
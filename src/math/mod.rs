@@ -0,0 +1,134 @@
+use std::ops::Mul;
+
+use cgmath::{Basis3, Rotation3, Matrix, SquareMatrix, Matrix3, Matrix4, Quaternion};
+use cgmath::{Vector2, Vector3, Vector4};
+
+use cgmath::{self, Rad, Point3, Point, Angle};
+
+pub use cgmath::{vec3, deg, EuclideanVector};
+
+pub use self::transform::Transform;
+pub use self::geometry::{Aabb, Sphere, Plane, Frustum, Ray};
+pub use self::uniform::{Unif, Color};
+
+mod transform;
+mod geometry;
+mod uniform;
+#[cfg(test)]
+pub mod test_util;
+
+pub type Vec2 = Vector2<f32>;
+pub type Vec3 = Vector3<f32>;
+pub type Vec4 = Vector4<f32>;
+
+#[derive(Debug, Clone, Copy)]
+pub struct Mat4(pub Matrix4<f32>);
+
+/// For normals, see `Mat4::normal_matrix`.
+#[derive(Debug, Clone, Copy)]
+pub struct Mat3(pub Matrix3<f32>);
+
+pub const X: Vec3 = Vec3 {
+    x: 1.0,
+    y: 0.0,
+    z: 0.0,
+};
+
+pub const Y: Vec3 = Vec3 {
+    x: 0.0,
+    y: 1.0,
+    z: 0.0,
+};
+
+pub const Z: Vec3 = Vec3 {
+    x: 0.0,
+    y: 0.0,
+    z: 1.0,
+};
+
+pub fn perspective<R: Into<Rad<f32>>>(fov: R, aspect_ratio: f32, near: f32, far: f32) -> Mat4 {
+    Mat4(cgmath::perspective(fov.into(), aspect_ratio, near, far))
+}
+
+/// A perspective projection with the far plane at infinity.
+pub fn infinite_perspective<R: Into<Rad<f32>>>(fov: R, aspect_ratio: f32, near: f32) -> Mat4 {
+    let fov: Rad<f32> = fov.into();
+    let f = 1.0 / (fov / 2.0).tan();
+    Mat4(Matrix4::new(f / aspect_ratio, 0.0, 0.0, 0.0,
+                      0.0, f, 0.0, 0.0,
+                      0.0, 0.0, -1.0, -1.0,
+                      0.0, 0.0, -2.0 * near, 0.0))
+}
+
+/// An infinite perspective projection mapping the near plane to depth 1 and
+/// infinity to depth 0 (0.5 in the depth buffer, without `glClipControl`).
+/// Draw with `DepthTest::IfMore` and clear the depth to 0.
+pub fn reversed_perspective<R: Into<Rad<f32>>>(fov: R, aspect_ratio: f32, near: f32) -> Mat4 {
+    let fov: Rad<f32> = fov.into();
+    let f = 1.0 / (fov / 2.0).tan();
+    Mat4(Matrix4::new(f / aspect_ratio, 0.0, 0.0, 0.0,
+                      0.0, f, 0.0, 0.0,
+                      0.0, 0.0, 0.0, -1.0,
+                      0.0, 0.0, near, 0.0))
+}
+
+pub fn orthographic(width: f32, height: f32, near: f32, far: f32) -> Mat4 {
+    let (x, y) = (width / 2.0, height / 2.0);
+    Mat4(cgmath::ortho(-x, x, -y, y, near, far))
+}
+
+
+pub fn id() -> Mat4 {
+    Mat4(Matrix4::from_scale(1.0))
+}
+
+pub fn look_at(eye: Vec3, center: Vec3, up: Vec3) -> Mat4 {
+    Mat4(Matrix4::look_at(Point3::from_vec(eye), Point3::from_vec(center), up))
+}
+
+impl Mat4 {
+    pub fn scale(&self, scale: f32) -> Mat4 {
+        Mat4(self.0 * Matrix4::from_scale(scale))
+    }
+
+    pub fn rotate<R: Into<Rad<f32>>>(&self, axis: Vec3, angle: R) -> Mat4 {
+        let rotation = Basis3::from_axis_angle(axis.normalize(), angle.into());
+        Mat4(self.0 * Matrix4::from(rotation.as_ref().clone()))
+    }
+
+    pub fn translate(&self, direction: Vec3) -> Mat4 {
+        Mat4(self.0 * Matrix4::from_translation(direction))
+    }
+
+    pub fn inverse(&self) -> Option<Mat4> {
+        self.0.invert().map(Mat4)
+    }
+
+    /// The inverse transpose of the upper left 3x3, which maps the normals of
+    /// the surfaces this matrix transforms. The identity if it is singular.
+    pub fn normal_matrix(&self) -> Mat3 {
+        let m = self.0;
+        let upper = Matrix3::from_cols(m.x.truncate(), m.y.truncate(), m.z.truncate());
+        Mat3(upper.invert().map_or(Matrix3::identity(), |inverse| inverse.transpose()))
+    }
+}
+
+impl Mul<Mat4> for Mat4 {
+    type Output = Mat4;
+
+    fn mul(self, other: Mat4) -> Mat4 {
+        Mat4(self.0 * other.0)
+    }
+}
+
+impl From<Quaternion<f32>> for Mat4 {
+    fn from(rotation: Quaternion<f32>) -> Mat4 {
+        Mat4(Matrix4::from(rotation))
+    }
+}
+
+impl From<Quaternion<f32>> for Mat3 {
+    fn from(rotation: Quaternion<f32>) -> Mat3 {
+        Mat3(Matrix3::from(rotation))
+    }
+}
@@ -0,0 +1,69 @@
+//! Assertions and fixtures shared by the tests of the math types and cameras.
+
+use super::{Vec3, Mat4, Mat3, Aabb, Transform, vec3, deg, Y, Z};
+
+pub const EPSILON: f32 = 1e-4;
+
+pub fn assert_vec3_close(actual: Vec3, expected: Vec3) {
+    let difference = actual - expected;
+    assert!(difference.x.abs() < EPSILON && difference.y.abs() < EPSILON &&
+            difference.z.abs() < EPSILON,
+            "{:?} differs from {:?}",
+            actual,
+            expected);
+}
+
+pub fn assert_mat4_close(actual: Mat4, expected: Mat4) {
+    let (a, e): ([[f32; 4]; 4], [[f32; 4]; 4]) = (actual.0.into(), expected.0.into());
+    for (column, expected_column) in a.iter().zip(e.iter()) {
+        for (x, y) in column.iter().zip(expected_column.iter()) {
+            assert!((x - y).abs() < EPSILON, "{:?} differs from {:?}", a, e);
+        }
+    }
+}
+
+pub fn assert_mat3_close(actual: Mat3, expected: Mat3) {
+    let (a, e): ([[f32; 3]; 3], [[f32; 3]; 3]) = (actual.0.into(), expected.0.into());
+    for (column, expected_column) in a.iter().zip(e.iter()) {
+        for (x, y) in column.iter().zip(expected_column.iter()) {
+            assert!((x - y).abs() < EPSILON, "{:?} differs from {:?}", a, e);
+        }
+    }
+}
+
+pub fn assert_transform_close(actual: &Transform, expected: &Transform) {
+    assert_vec3_close(actual.translation, expected.translation);
+    assert_vec3_close(actual.scale, expected.scale);
+    // q and -q are the same rotation.
+    let dot = actual.rotation.dot(expected.rotation);
+    assert!((dot.abs() - 1.0).abs() < EPSILON,
+            "{:?} differs from {:?}",
+            actual.rotation,
+            expected.rotation);
+}
+
+pub fn triangle() -> (Vec3, Vec3, Vec3) {
+    (vec3(0.0, 0.0, 0.0), vec3(1.0, 0.0, 0.0), vec3(0.0, 1.0, 0.0))
+}
+
+pub fn unit_box() -> Aabb {
+    Aabb::new(vec3(-1.0, -1.0, -1.0), vec3(1.0, 1.0, 1.0))
+}
+
+pub fn transform(translation: Vec3, axis: Vec3, angle: f32, scale: Vec3) -> Transform {
+    Transform {
+        translation: translation,
+        scale: scale,
+        ..Transform::from_rotation(axis, deg(angle))
+    }
+}
+
+pub fn transforms() -> Vec<Transform> {
+    vec![Transform::identity(),
+         Transform::from_translation(vec3(1.0, -2.0, 3.0)),
+         Transform::from_rotation(Y, deg(90.0)),
+         Transform::from_scale(vec3(2.0, 2.0, 2.0)),
+         transform(vec3(1.0, -2.0, 3.0), vec3(1.0, 2.0, 3.0), 70.0, vec3(0.5, 0.5, 0.5)),
+         transform(vec3(-4.0, 0.0, 1.0), vec3(-1.0, 1.0, 0.0), 200.0, vec3(1.0, 2.0, 3.0)),
+         transform(vec3(0.0, 5.0, 0.0), Z, -30.0, vec3(-1.0, 0.5, 4.0))]
+}
@@ -0,0 +1,243 @@
+use std::ops::Mul;
+
+use cgmath::{Matrix3, Matrix4, Quaternion, Vector, SquareMatrix, EuclideanVector, Rotation3, Rad};
+
+use super::{Vec3, Mat4, Mat3, vec3};
+
+
+/// Scales, then rotates, then translates.
+///
+/// Composing and inverting transforms with a non-uniform scale under a
+/// rotation would need a shear, which is left out: the result is exact when
+/// the scale is uniform.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Transform {
+    pub translation: Vec3,
+    pub rotation: Quaternion<f32>,
+    pub scale: Vec3,
+}
+
+impl Default for Transform {
+    fn default() -> Transform {
+        Transform::identity()
+    }
+}
+
+impl Transform {
+    pub fn identity() -> Transform {
+        Transform::new(Vec3::zero(), Quaternion::one(), vec3(1.0, 1.0, 1.0))
+    }
+
+    pub fn new(translation: Vec3, rotation: Quaternion<f32>, scale: Vec3) -> Transform {
+        Transform {
+            translation: translation,
+            rotation: rotation,
+            scale: scale,
+        }
+    }
+
+    pub fn from_translation(translation: Vec3) -> Transform {
+        Transform { translation: translation, ..Transform::identity() }
+    }
+
+    pub fn from_rotation<R: Into<Rad<f32>>>(axis: Vec3, angle: R) -> Transform {
+        let rotation = Quaternion::from_axis_angle(axis.normalize(), angle.into());
+        Transform { rotation: rotation, ..Transform::identity() }
+    }
+
+    pub fn from_scale(scale: Vec3) -> Transform {
+        Transform { scale: scale, ..Transform::identity() }
+    }
+
+    /// Splits `matrix` into scale, rotation and translation. A mirroring
+    /// matrix gets a negative x scale. Shear and projection are lost.
+    pub fn from_matrix(matrix: &Mat4) -> Transform {
+        let m = matrix.0;
+        let (x, y, z) = (m.x.truncate(), m.y.truncate(), m.z.truncate());
+        let mut scale = vec3(x.length(), y.length(), z.length());
+        if x.cross(y).dot(z) < 0.0 {
+            scale.x = -scale.x;
+        }
+        let (x, y, z) = (x / scale.x, y / scale.y, z / scale.z);
+        let rotation = Matrix3::new(x.x, x.y, x.z, y.x, y.y, y.z, z.x, z.y, z.z);
+        Transform {
+            translation: m.w.truncate(),
+            rotation: Quaternion::from(rotation).normalize(),
+            scale: scale,
+        }
+    }
+
+    pub fn matrix(&self) -> Mat4 {
+        let scale = Matrix4::from_nonuniform_scale(self.scale.x, self.scale.y, self.scale.z);
+        Mat4(Matrix4::from_translation(self.translation) * Matrix4::from(self.rotation) * scale)
+    }
+
+    /// The matrix for the normals, the inverse transpose of the rotation and
+    /// scale.
+    pub fn normal_matrix(&self) -> Mat3 {
+        let inverse = vec3(1.0 / self.scale.x, 1.0 / self.scale.y, 1.0 / self.scale.z);
+        Mat3(Matrix3::from(self.rotation) * Matrix3::from_diagonal(inverse))
+    }
+
+    /// `other` applied after `self`, the same as `other * self`.
+    pub fn then(&self, other: &Transform) -> Transform {
+        *other * *self
+    }
+
+    /// The transform undoing this one, only when the scale is uniform.
+    pub fn inverse(&self) -> Transform {
+        let rotation = self.rotation.conjugate();
+        let scale = vec3(1.0 / self.scale.x, 1.0 / self.scale.y, 1.0 / self.scale.z);
+        Transform {
+            translation: -(rotation * self.translation) * scale,
+            rotation: rotation,
+            scale: scale,
+        }
+    }
+
+    /// Linear interpolation of the translation and scale and spherical one of
+    /// the rotation, `self` at 0 and `other` at 1.
+    pub fn interpolate(&self, other: &Transform, t: f32) -> Transform {
+        // Take the shorter way around.
+        let rotation = if self.rotation.dot(other.rotation) < 0.0 {
+            -other.rotation
+        } else {
+            other.rotation
+        };
+        Transform {
+            translation: self.translation.lerp(other.translation, t),
+            rotation: self.rotation.slerp(rotation, t).normalize(),
+            scale: self.scale.lerp(other.scale, t),
+        }
+    }
+
+    pub fn transform_point(&self, point: Vec3) -> Vec3 {
+        self.rotation * (point * self.scale) + self.translation
+    }
+
+    pub fn transform_vector(&self, vector: Vec3) -> Vec3 {
+        self.rotation * (vector * self.scale)
+    }
+}
+
+/// `a * b` applies `b` first, like the matrices, when the scale of `a` is
+/// uniform.
+impl Mul<Transform> for Transform {
+    type Output = Transform;
+
+    fn mul(self, other: Transform) -> Transform {
+        Transform {
+            translation: self.transform_point(other.translation),
+            rotation: (self.rotation * other.rotation).normalize(),
+            scale: self.scale * other.scale,
+        }
+    }
+}
+
+impl From<Transform> for Mat4 {
+    fn from(transform: Transform) -> Mat4 {
+        transform.matrix()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cgmath::{Matrix, SquareMatrix, Matrix3, Matrix4};
+
+    use math::{Mat4, Mat3, id, vec3, deg, look_at, X, Y, Z};
+    use math::test_util::{assert_vec3_close, assert_mat4_close, assert_mat3_close};
+    use math::test_util::{assert_transform_close, transform, transforms};
+    use super::Transform;
+
+    #[test]
+    fn matrix_matches_mat4() {
+        let t = transform(vec3(1.0, -2.0, 3.0), vec3(1.0, 2.0, 3.0), 70.0, vec3(3.0, 3.0, 3.0));
+        let expected = id().translate(vec3(1.0, -2.0, 3.0)).rotate(vec3(1.0, 2.0, 3.0), deg(70.0));
+        assert_mat4_close(t.matrix(), expected.scale(3.0));
+        assert_mat4_close(Mat4::from(t), t.matrix());
+    }
+
+    #[test]
+    fn matrix_round_trip() {
+        for t in transforms() {
+            assert_transform_close(&Transform::from_matrix(&t.matrix()), &t);
+        }
+    }
+
+    #[test]
+    fn mirrored_matrix_round_trip() {
+        // Mirrored on y, which comes back as a mirror on x and a rotation.
+        let matrix = Mat4(Matrix4::from_nonuniform_scale(2.0, -3.0, 4.0)) *
+                     id().rotate(vec3(1.0, 1.0, 0.0), deg(40.0));
+        let t = Transform::from_matrix(&matrix);
+        assert!(t.scale.x < 0.0 && t.scale.y > 0.0 && t.scale.z > 0.0);
+        assert_mat4_close(t.matrix(), matrix);
+    }
+
+    #[test]
+    fn inverse_is_identity() {
+        let uniform = |t: &Transform| t.scale.x == t.scale.y && t.scale.y == t.scale.z;
+        for t in transforms().into_iter().filter(uniform) {
+            assert_mat4_close(t.matrix() * t.inverse().matrix(), id());
+            assert_mat4_close(t.inverse().matrix() * t.matrix(), id());
+        }
+    }
+
+    #[test]
+    fn inverse_with_uniform_scale() {
+        let t = transform(vec3(1.0, -2.0, 3.0), vec3(1.0, 2.0, 3.0), 70.0, vec3(0.5, 0.5, 0.5));
+        assert_transform_close(&(t.inverse() * t), &Transform::identity());
+        assert_mat4_close(t.inverse().matrix(), t.matrix().inverse().unwrap());
+        let point = vec3(4.0, 5.0, -6.0);
+        assert_vec3_close(t.inverse().transform_point(t.transform_point(point)), point);
+        assert_vec3_close(t.transform_point(t.inverse().transform_point(point)), point);
+    }
+
+    #[test]
+    fn then_applies_other_after() {
+        let (a, b) = (Transform::from_translation(X), Transform::from_rotation(Z, deg(90.0)));
+        assert_vec3_close(a.then(&b).transform_point(X), vec3(0.0, 2.0, 0.0));
+        assert_mat4_close((a * b).matrix(), a.matrix() * b.matrix());
+    }
+
+    #[test]
+    fn interpolate_endpoints() {
+        let a = transform(vec3(1.0, -2.0, 3.0), vec3(1.0, 2.0, 3.0), 70.0, vec3(1.0, 2.0, 3.0));
+        let b = transform(vec3(-4.0, 0.0, 1.0), vec3(-1.0, 1.0, 0.0), 200.0, vec3(0.5, 1.0, 4.0));
+        assert_transform_close(&a.interpolate(&b, 0.0), &a);
+        assert_transform_close(&a.interpolate(&b, 1.0), &b);
+        assert_mat4_close(a.interpolate(&b, 0.0).matrix(), a.matrix());
+        assert_mat4_close(a.interpolate(&b, 1.0).matrix(), b.matrix());
+
+        let middle = a.interpolate(&b, 0.5);
+        assert_vec3_close(middle.translation, vec3(-1.5, -1.0, 2.0));
+        assert_vec3_close(middle.scale, vec3(0.75, 1.5, 3.5));
+    }
+
+    #[test]
+    fn interpolate_takes_shorter_way() {
+        let a = Transform::from_rotation(Z, deg(10.0));
+        let b = Transform::from_rotation(Z, deg(350.0));
+        let flipped = Transform { rotation: -b.rotation, ..b };
+        let expected = Transform::identity();
+        assert_transform_close(&a.interpolate(&b, 0.5), &expected);
+        assert_transform_close(&a.interpolate(&flipped, 0.5), &expected);
+    }
+
+    /// The upper left 3x3 of the transpose of the inverse.
+    fn inverse_transpose(matrix: Mat4) -> Mat3 {
+        let m = matrix.0.invert().unwrap().transpose();
+        Mat3(Matrix3::from_cols(m.x.truncate(), m.y.truncate(), m.z.truncate()))
+    }
+
+    #[test]
+    fn normal_matrix_with_non_uniform_scale() {
+        let view = look_at(vec3(3.0, 4.0, 5.0), vec3(0.0, 1.0, 0.0), Y);
+        for t in transforms() {
+            let view_model = view * t.matrix();
+            assert_mat3_close(t.normal_matrix(), inverse_transpose(t.matrix()));
+            assert_mat3_close(t.matrix().normal_matrix(), inverse_transpose(t.matrix()));
+            assert_mat3_close(view_model.normal_matrix(), inverse_transpose(view_model));
+        }
+    }
+}
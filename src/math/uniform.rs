@@ -0,0 +1,91 @@
+use cgmath::{Matrix4, Quaternion, Vector2, Vector3, Vector4};
+use glium::uniforms::{AsUniformValue, UniformValue};
+
+use super::{Mat4, Mat3};
+
+
+/// A math value as a uniform, since the cgmath types cannot implement
+/// `AsUniformValue` themselves: `uniform! { light: Unif(light) }`.
+/// Quaternions become rotation matrices, `mat4` in the shaders.
+#[derive(Debug, Clone, Copy)]
+pub struct Unif<T>(pub T);
+
+impl AsUniformValue for Unif<Vector2<f32>> {
+    fn as_uniform_value(&self) -> UniformValue {
+        UniformValue::Vec2(self.0.into())
+    }
+}
+
+impl AsUniformValue for Unif<Vector3<f32>> {
+    fn as_uniform_value(&self) -> UniformValue {
+        UniformValue::Vec3(self.0.into())
+    }
+}
+
+impl AsUniformValue for Unif<Vector4<f32>> {
+    fn as_uniform_value(&self) -> UniformValue {
+        UniformValue::Vec4(self.0.into())
+    }
+}
+
+impl AsUniformValue for Unif<Quaternion<f32>> {
+    fn as_uniform_value(&self) -> UniformValue {
+        UniformValue::Mat4(Matrix4::from(self.0).into())
+    }
+}
+
+/// A linear RGB color, a `vec3` in the shaders.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Color {
+    pub r: f32,
+    pub g: f32,
+    pub b: f32,
+}
+
+impl Color {
+    pub fn rgb(r: f32, g: f32, b: f32) -> Color {
+        Color { r: r, g: g, b: b }
+    }
+
+    pub fn gray(value: f32) -> Color {
+        Color::rgb(value, value, value)
+    }
+
+    pub fn white() -> Color {
+        Color::gray(1.0)
+    }
+
+    pub fn black() -> Color {
+        Color::gray(0.0)
+    }
+}
+
+impl From<[f32; 3]> for Color {
+    fn from(rgb: [f32; 3]) -> Color {
+        Color::rgb(rgb[0], rgb[1], rgb[2])
+    }
+}
+
+impl From<Color> for [f32; 3] {
+    fn from(color: Color) -> [f32; 3] {
+        [color.r, color.g, color.b]
+    }
+}
+
+impl AsUniformValue for Color {
+    fn as_uniform_value(&self) -> UniformValue {
+        UniformValue::Vec3((*self).into())
+    }
+}
+
+impl AsUniformValue for Mat4 {
+    fn as_uniform_value(&self) -> UniformValue {
+        UniformValue::Mat4(self.0.into())
+    }
+}
+
+impl AsUniformValue for Mat3 {
+    fn as_uniform_value(&self) -> UniformValue {
+        UniformValue::Mat3(self.0.into())
+    }
+}
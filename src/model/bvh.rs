@@ -0,0 +1,127 @@
+use std::f32;
+use std::cmp::Ordering;
+
+use math::{Vec3, Aabb, Ray};
+
+/// Triangles per leaf, at most.
+const LEAF_SIZE: usize = 4;
+
+
+/// A bounding volume hierarchy over the triangles of a mesh, for ray casts.
+#[derive(Debug)]
+pub struct Bvh {
+    nodes: Vec<Node>,
+    /// Triangle numbers, the leaves refer to ranges of it.
+    triangles: Vec<usize>,
+}
+
+/// A box around the triangles `first..first + count` of a leaf. An inner node
+/// has `count == 0`, its children are the next node and the node `first`.
+#[derive(Debug)]
+struct Node {
+    bounds: Aabb,
+    first: usize,
+    count: usize,
+}
+
+impl Bvh {
+    /// `indices` lists the corners of the triangles, three per triangle.
+    pub fn new(positions: &[Vec3], indices: &[u32]) -> Bvh {
+        let corners = |t: usize| {
+            (positions[indices[3 * t] as usize],
+             positions[indices[3 * t + 1] as usize],
+             positions[indices[3 * t + 2] as usize])
+        };
+        let centers = (0..indices.len() / 3)
+                          .map(|t| {
+                              let (a, b, c) = corners(t);
+                              (a + b + c) / 3.0
+                          })
+                          .collect::<Vec<_>>();
+        let mut result = Bvh {
+            nodes: Vec::new(),
+            triangles: (0..centers.len()).collect(),
+        };
+        let count = centers.len();
+        if count > 0 {
+            result.build(&corners, &centers, 0, count);
+        }
+        result
+    }
+
+    /// Adds the node for the triangles `first..first + count`, splitting them
+    /// at the median of the longest axis of their centers.
+    fn build<F>(&mut self, corners: &F, centers: &[Vec3], first: usize, count: usize)
+        where F: Fn(usize) -> (Vec3, Vec3, Vec3)
+    {
+        let mut bounds = Aabb::empty();
+        let mut center_bounds = Aabb::empty();
+        for &t in &self.triangles[first..first + count] {
+            let (a, b, c) = corners(t);
+            bounds = bounds.grow(a).grow(b).grow(c);
+            center_bounds = center_bounds.grow(centers[t]);
+        }
+        let index = self.nodes.len();
+        self.nodes.push(Node {
+            bounds: bounds,
+            first: first,
+            count: count,
+        });
+        if count <= LEAF_SIZE {
+            return;
+        }
+
+        let extent = center_bounds.size();
+        let axis = if extent.x >= extent.y && extent.x >= extent.z {
+            0
+        } else if extent.y >= extent.z {
+            1
+        } else {
+            2
+        };
+        self.triangles[first..first + count].sort_by(|&a, &b| {
+            centers[a][axis].partial_cmp(&centers[b][axis]).unwrap_or(Ordering::Equal)
+        });
+        let half = count / 2;
+        self.build(corners, centers, first, half);
+        let right = self.nodes.len();
+        self.build(corners, centers, first + half, count - half);
+        self.nodes[index].first = right;
+        self.nodes[index].count = 0;
+    }
+
+    /// The nearest triangle hit by `ray` as `(t, triangle)`.
+    pub fn intersect(&self,
+                     ray: &Ray,
+                     positions: &[Vec3],
+                     indices: &[u32])
+                     -> Option<(f32, usize)> {
+        let mut nearest: Option<(f32, usize)> = None;
+        let mut stack = vec![0];
+        while let Some(index) = stack.pop() {
+            let node = match self.nodes.get(index) {
+                Some(node) => node,
+                None => continue,
+            };
+            let limit = nearest.map_or(f32::INFINITY, |(t, _)| t);
+            match ray.intersect_aabb(&node.bounds) {
+                Some((near, _)) if near <= limit => {}
+                _ => continue,
+            }
+            if node.count == 0 {
+                stack.push(node.first);
+                stack.push(index + 1);
+                continue;
+            }
+            for &triangle in &self.triangles[node.first..node.first + node.count] {
+                let corner = |i: usize| positions[indices[3 * triangle + i] as usize];
+                if let Some(t) = ray.intersect_triangle(corner(0), corner(1), corner(2)) {
+                    if nearest.map_or(true, |(nearest, _)| t < nearest) {
+                        nearest = Some((t, triangle));
+                    }
+                }
+            }
+        }
+        nearest
+    }
+}
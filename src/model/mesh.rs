@@ -5,12 +5,20 @@ use glium::{VertexBuffer, Surface, Texture2d, Program, DrawParameters};
 use tobj;
 
 use {Result};
+use math::{Vec3, Aabb, Ray, vec3};
+use super::bvh::Bvh;
 
 
 #[derive(Debug)]
 pub struct Mesh {
+    pub name: String,
     vertex_buffer: VertexBuffer<Vertex>,
     index_buffer: IndexBuffer<u32>,
+    bounds: Aabb,
+    /// Copies of the geometry for ray casts.
+    positions: Vec<Vec3>,
+    indices: Vec<u32>,
+    bvh: Bvh,
     pub material_id: Option<usize>,
 }
 
@@ -32,14 +40,34 @@ impl Mesh {
                 texture: [get(&mesh.texcoords, 2 * i), get(&mesh.texcoords, 2 * i + 1)],
             })
         }
+        let positions = mesh.positions
+                            .chunks(3)
+                            .map(|p| vec3(p[0], p[1], p[2]))
+                            .collect::<Vec<_>>();
         Ok(Mesh {
+            name: model.name.clone(),
             vertex_buffer: try!(VertexBuffer::new(facade, &vertices)),
             index_buffer: try!(IndexBuffer::new(facade,
                                                 PrimitiveType::TrianglesList,
                                                 &mesh.indices)),
+            bounds: Aabb::from_points(positions.iter().cloned()),
+            bvh: Bvh::new(&positions, &mesh.indices),
+            positions: positions,
+            indices: mesh.indices.clone(),
             material_id: model.mesh.material_id,
         })
     }
+
+    /// The axis aligned bounding box, in model space.
+    pub fn bounds(&self) -> Aabb {
+        self.bounds
+    }
+
+    /// The nearest triangle hit by `ray` as `(t, triangle)`.
+    pub fn intersect(&self, ray: &Ray) -> Option<(f32, usize)> {
+        self.bvh.intersect(ray, &self.positions, &self.indices)
+    }
+
     pub fn draw<S: Surface, U: Uniforms>(&self,
                                          surface: &mut S,
                                          params: &DrawParameters,
@@ -9,12 +9,14 @@ use glium::texture::Texture2d;
 use glium::{Surface, Program, DrawParameters};
 use itertools::Itertools;
 
-use assets::load_texture;
+use assets::{AssetManager, load_texture_file};
 use tobj::{self, Material};
 
 use {Result};
+use math::{Vec3, Mat4, Aabb, Frustum, Ray};
 
 mod mesh;
+mod bvh;
 
 use self::mesh::Mesh;
 
@@ -42,55 +44,122 @@ pub struct Model {
     meshes: Vec<Mesh>,
     materials: Vec<Material>,
     textures: Textures,
+    bounds: Aabb,
 }
 
 impl Model {
+    /// Loads `models/<path>` from the asset directories, see `AssetManager`.
+    /// The textures are next to the OBJ file.
     pub fn load<P: AsRef<Path>>(facade: &GlutinFacade, path: P) -> Result<Model> {
-        let models_path = Path::new("./assets/models/");
-        let model_path = models_path.join(path);
+        let model_path = try!(AssetManager::current().find(Path::new("models").join(path)));
         let base = model_path.parent().expect("Invalid model path");
 
         let (models, materials) = try!(tobj::load_obj(&model_path));
         let meshes = try!(models.into_iter()
                                 .map(|m| Mesh::from_obj(facade, m))
                                 .collect::<Result<Vec<_>>>());
-        let textures = load_textures(facade, &base, &materials);
+        let textures = try!(load_textures(facade, &base, &materials));
+        let bounds = meshes.iter().fold(Aabb::empty(), |bounds, m| bounds.union(&m.bounds()));
         Ok(Model {
             meshes: meshes,
             materials: materials,
             textures: textures,
+            bounds: bounds,
         })
     }
 
+    /// The axis aligned bounding box, in model space.
+    pub fn bounds(&self) -> Aabb {
+        self.bounds
+    }
+
+    /// Draws the meshes with a bounding box inside `frustum`, which is in
+    /// model space, see `Api::frustum`. One draw call is made per mesh drawn.
     pub fn draw<S: Surface, U: Uniforms>(&self,
                                          surface: &mut S,
                                          params: &DrawParameters,
                                          program: &Program,
-                                         uniforms: &U)
-                                         -> Result<()> {
+                                         uniforms: &U,
+                                         frustum: &Frustum)
+                                         -> Result<DrawCount> {
+        let mut count = DrawCount::default();
         for m in &self.meshes {
+            if !frustum.intersects_aabb(&m.bounds()) {
+                count.culled += 1;
+                continue;
+            }
             let material = m.material_id.map(|i| &self.materials[i]);
             let tex = material.map(|m| &self.textures[&m.diffuse_texture]);
-            try!(m.draw(surface, params, program, uniforms, material, tex))
+            try!(m.draw(surface, params, program, uniforms, material, tex));
+            count.drawn += 1;
+        }
+        Ok(count)
+    }
+
+    /// The nearest point where `ray`, in world space, hits the model placed
+    /// with the `model` matrix. Only the meshes whose bounding box is hit are
+    /// tested, through their bounding volume hierarchies.
+    pub fn pick(&self, ray: &Ray, model: Mat4) -> Option<Hit> {
+        let local = match model.inverse() {
+            Some(inverse) => ray.transform(&inverse),
+            None => return None,
+        };
+        let mut nearest: Option<Hit> = None;
+        for (i, mesh) in self.meshes.iter().enumerate() {
+            if local.intersect_aabb(&mesh.bounds()).is_none() {
+                continue;
+            }
+            if let Some((t, triangle)) = mesh.intersect(&local) {
+                if nearest.as_ref().map_or(true, |hit| t < hit.t) {
+                    nearest = Some(Hit {
+                        mesh: i,
+                        name: &mesh.name,
+                        material: mesh.material_id.map(|m| &self.materials[m]),
+                        triangle: triangle,
+                        t: t,
+                        position: ray.at(t),
+                    });
+                }
+            }
         }
-        Ok(())
+        nearest
     }
 }
 
+/// Where a ray hits a `Model`, see `Model::pick`.
+#[derive(Debug)]
+pub struct Hit<'a> {
+    /// The index of the mesh in the model.
+    pub mesh: usize,
+    /// The name of the mesh in the OBJ file.
+    pub name: &'a str,
+    pub material: Option<&'a Material>,
+    pub triangle: usize,
+    /// Where the hit is along the ray, see `Ray::at`.
+    pub t: f32,
+    /// The hit point in world space.
+    pub position: Vec3,
+}
+
+/// Meshes drawn and skipped by `Model::draw`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DrawCount {
+    pub drawn: usize,
+    pub culled: usize,
+}
+
 fn load_textures(facade: &GlutinFacade,
                  base_path: &Path,
                  materials: &[tobj::Material])
-                 -> Textures {
-    materials.iter()
-             .filter_map(|material| {
-                 match material.diffuse_texture.as_ref() {
-                     "" => None,
-                     tex => {
-                         let image = load_texture(base_path.join(tex));
-                         let texture = Texture2d::new(facade, image).unwrap();
-                         Some((tex.to_owned(), texture))
-                     }
-                 }
-             })
-             .collect::<HashMap<_, _>>()
+                 -> Result<Textures> {
+    let mut result = HashMap::new();
+    for material in materials {
+        let tex = &material.diffuse_texture;
+        if tex.is_empty() || result.contains_key(tex) {
+            continue;
+        }
+        let image = try!(load_texture_file(base_path.join(tex)));
+        result.insert(tex.clone(), try!(Texture2d::new(facade, image)));
+    }
+    Ok(result)
 }
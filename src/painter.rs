@@ -1,30 +1,84 @@
+use std::cell::RefCell;
+use std::path::PathBuf;
+
 use glium::{Surface, DrawParameters};
 use glium::glutin::Event;
 use glium::backend::glutin_backend::GlutinFacade;
 use math::*;
 
 use result::Result;
+use camera::Camera;
+use input::Actions;
+use profiler::Profiler;
+use golden::Shot;
 
 
 pub trait Painter: Sized {
     fn new(facade: &GlutinFacade) -> Result<Self>;
     fn draw<S: Surface>(&self, api: &mut Api<S>) -> Result<()>;
-    fn process_event(&mut self, _event: Event, _delta_seconds: f32) {}
+    fn process_event(&mut self, _event: Event) {}
+    /// Advances the simulation by a fixed step of `dt` seconds.
+    fn update(&mut self, _dt: f32, _actions: &Actions) {}
+    /// Moves the cameras driven by the user while the simulation is paused.
+    fn update_camera(&mut self, dt: f32, actions: &Actions) {
+        if let Some(camera) = self.camera_mut() {
+            camera.update(dt, actions);
+        }
+    }
+    /// Called once before the first frame and whenever the window is resized,
+    /// so that size dependent render targets can be reallocated.
+    fn resize(&mut self, _facade: &GlutinFacade, _width: u32, _height: u32) -> Result<()> {
+        Ok(())
+    }
     fn clear_color() -> (f32, f32, f32) {
         (0.2, 0.02, 0.8)
     }
+
+    /// The camera the frame is seen from. Its projection decides the depth
+    /// test of `Api::default_params` and the depth the target is cleared to.
+    fn camera(&self) -> Option<&Camera> {
+        None
+    }
+
+    /// The same camera as `camera`, which golden-image shots and bookmarks
+    /// move and whose pose the `keyframe` action records.
+    fn camera_mut(&mut self) -> Option<&mut Camera> {
+        None
+    }
+
+    /// Frames rendered and compared by `App::golden`, in any order.
+    fn golden_shots() -> Vec<Shot> {
+        vec![Shot::new("default", 0.0)]
+    }
 }
 
 pub struct Api<'a, S: Surface + 'a> {
     pub facade: &'a GlutinFacade,
+    pub profiler: &'a Profiler,
+    /// Set by `Api::screenshot`, the `App` saves the frame after it is finished.
+    pub screenshot: &'a RefCell<Option<PathBuf>>,
     pub surface: &'a mut S,
     pub aspect_ratio: f32,
+    /// Simulation time of the last update.
     pub time: f32,
+    /// How far the frame is between the last update and the next one, in `[0, 1)`.
+    pub alpha: f32,
     pub default_params: DrawParameters<'static>,
 }
 
 impl<'a, S: Surface> Api<'a, S> {
-    pub fn projection(&self) -> Mat4 {
-        perspective(deg(45.0), self.aspect_ratio, 0.1, 100.0)
+    /// Saves the frame as PNG at `path` once it is finished.
+    pub fn screenshot<P: Into<PathBuf>>(&self, path: P) {
+        *self.screenshot.borrow_mut() = Some(path.into());
+    }
+
+    /// The projection of `camera` for this target.
+    pub fn projection(&self, camera: &Camera) -> Mat4 {
+        camera.projection().matrix(self.aspect_ratio)
+    }
+
+    /// What `camera` sees of this target, in the space of `model`.
+    pub fn frustum(&self, camera: &Camera, model: Mat4) -> Frustum {
+        Frustum::from_matrix(&(self.projection(camera) * camera.view() * model))
     }
 }
@@ -0,0 +1,344 @@
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, VecDeque};
+use std::fs::File;
+use std::io::prelude::*;
+use std::mem;
+use std::path::Path;
+
+use glium::DrawParameters;
+use glium::backend::glutin_backend::GlutinFacade;
+use glium::draw_parameters::{TimeElapsedQuery, PrimitivesGeneratedQuery};
+use time;
+
+use {Result, oops};
+
+/// Number of samples in the rolling averages.
+const WINDOW: usize = 60;
+
+/// Events kept for the trace export, older ones are dropped.
+const MAX_TRACE_EVENTS: usize = 100000;
+
+const CPU_TRACK: u32 = 1;
+const GPU_TRACK: u32 = 2;
+
+
+/// Collects CPU and GPU timings of named scopes opened by painters.
+///
+/// GPU time and triangle counts come from OpenGL queries attached to the draw
+/// calls made with `Scope::params`. Only one query of a kind can be active at a
+/// time, so scopes using `params` should not be nested.
+///
+/// A disabled profiler makes no queries and records nothing, its scopes cost
+/// next to nothing.
+pub struct Profiler {
+    facade: GlutinFacade,
+    enabled: bool,
+    origin: f64,
+    pending: RefCell<Vec<Sample>>,
+    stats: RefCell<Stats>,
+    trace: RefCell<VecDeque<TraceEvent>>,
+}
+
+impl Profiler {
+    pub fn new(facade: &GlutinFacade) -> Profiler {
+        Profiler {
+            facade: facade.clone(),
+            enabled: true,
+            origin: time::precise_time_s(),
+            pending: RefCell::new(Vec::new()),
+            stats: RefCell::new(Stats::default()),
+            trace: RefCell::new(VecDeque::new()),
+        }
+    }
+
+    pub fn with_enabled(self, enabled: bool) -> Profiler {
+        Profiler { enabled: enabled, ..self }
+    }
+
+    /// Opens a scope measuring both CPU and GPU time. If timer queries are not
+    /// supported, only CPU time is measured.
+    pub fn scope(&self, name: &'static str) -> Scope {
+        if !self.enabled {
+            return self.cpu_scope(name);
+        }
+        Scope {
+            time_elapsed: TimeElapsedQuery::new(&self.facade).ok(),
+            primitives: PrimitivesGeneratedQuery::new(&self.facade).ok(),
+            ..self.cpu_scope(name)
+        }
+    }
+
+    /// Opens a scope measuring only CPU time.
+    pub fn cpu_scope(&self, name: &'static str) -> Scope {
+        Scope {
+            profiler: self,
+            name: name,
+            start: time::precise_time_s(),
+            time_elapsed: None,
+            primitives: None,
+            used: Cell::new(false),
+            draws: Cell::new(0),
+            culled: Cell::new(0),
+        }
+    }
+
+    /// Collects the results of the queries which are ready. Results usually
+    /// become available a frame or two after the scope was closed.
+    pub fn end_frame(&self) {
+        let pending = mem::replace(&mut *self.pending.borrow_mut(), Vec::new());
+        let (ready, waiting): (Vec<_>, Vec<_>) = pending.into_iter().partition(|s| s.is_ready());
+        *self.pending.borrow_mut() = waiting;
+
+        let mut stats = self.stats.borrow_mut();
+        let mut trace = self.trace.borrow_mut();
+        for sample in ready {
+            let gpu = sample.time_elapsed.as_ref().map(|q| q.get() as f64 / 1e6);
+            let triangles = sample.primitives.as_ref().map(|q| q.get() as usize);
+            let start = (sample.start - self.origin) * 1e6;
+            trace.push_back(TraceEvent {
+                name: sample.name,
+                track: CPU_TRACK,
+                start_us: start,
+                duration_us: sample.cpu * 1e3,
+            });
+            if let Some(gpu) = gpu {
+                trace.push_back(TraceEvent {
+                    name: sample.name,
+                    track: GPU_TRACK,
+                    start_us: start,
+                    duration_us: gpu * 1e3,
+                });
+            }
+            while trace.len() > MAX_TRACE_EVENTS {
+                trace.pop_front();
+            }
+            stats.add(sample.name,
+                      Measurement {
+                          cpu: sample.cpu,
+                          gpu: gpu,
+                          draws: sample.draws,
+                          culled: sample.culled,
+                          triangles: triangles,
+                      });
+        }
+    }
+
+    /// Rolling averages of all scopes in the order they were first seen.
+    pub fn averages(&self) -> Vec<(&'static str, Average)> {
+        let stats = self.stats.borrow();
+        stats.order
+             .iter()
+             .map(|&name| (name, stats.scopes[&name].average()))
+             .collect()
+    }
+
+    /// A one line summary, like `g-buffer 1.20/3.40ms 64dc 31k tri 12 culled | lighting ...`.
+    pub fn summary(&self) -> String {
+        self.averages()
+            .iter()
+            .map(|&(name, ref avg)| {
+                let gpu = match avg.gpu {
+                    Some(gpu) => format!("{:.2}", gpu),
+                    None => "-".to_owned(),
+                };
+                let triangles = match avg.triangles {
+                    Some(t) => format!(" {}k tri", t / 1000),
+                    None => String::new(),
+                };
+                let culled = match avg.culled {
+                    0 => String::new(),
+                    n => format!(" {} culled", n),
+                };
+                format!("{} {:.2}/{}ms {}dc{}{}",
+                        name,
+                        avg.cpu,
+                        gpu,
+                        avg.draws,
+                        triangles,
+                        culled)
+            })
+            .collect::<Vec<_>>()
+            .join(" | ")
+    }
+
+    /// Writes the recorded scopes in the Chrome trace event format, which can be
+    /// opened in `chrome://tracing`. CPU and GPU times are shown as two threads,
+    /// GPU events are placed at the start of the corresponding CPU scope.
+    pub fn write_trace<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        let events = self.trace
+                         .borrow()
+                         .iter()
+                         .map(|e| {
+                             format!("{{\"name\":\"{}\",\"cat\":\"{}\",\"ph\":\"X\",\"pid\":1,\
+                                      \"tid\":{},\"ts\":{:.3},\"dur\":{:.3}}}",
+                                     e.name,
+                                     if e.track == CPU_TRACK { "cpu" } else { "gpu" },
+                                     e.track,
+                                     e.start_us,
+                                     e.duration_us)
+                         })
+                         .collect::<Vec<_>>();
+        let thread_names = format!("{{\"name\":\"thread_name\",\"ph\":\"M\",\"pid\":1,\"tid\":{},\
+                                    \"args\":{{\"name\":\"CPU\"}}}},\
+                                    {{\"name\":\"thread_name\",\"ph\":\"M\",\"pid\":1,\"tid\":{},\
+                                    \"args\":{{\"name\":\"GPU\"}}}}",
+                                   CPU_TRACK,
+                                   GPU_TRACK);
+        let json = format!("{{\"traceEvents\":[{}{}{}]}}\n",
+                           thread_names,
+                           if events.is_empty() { "" } else { ",\n" },
+                           events.join(",\n"));
+
+        let mut file = try!(File::create(path)
+                                .map_err(|e| oops(format!("failed to write {}", path.display()), e)));
+        try!(file.write_all(json.as_bytes())
+                 .map_err(|e| oops(format!("failed to write {}", path.display()), e)));
+        info!("Saved trace to {}", path.display());
+        Ok(())
+    }
+}
+
+/// A named region of a frame, measured from `Profiler::scope` until drop.
+pub struct Scope<'a> {
+    profiler: &'a Profiler,
+    name: &'static str,
+    start: f64,
+    time_elapsed: Option<TimeElapsedQuery>,
+    primitives: Option<PrimitivesGeneratedQuery>,
+    used: Cell<bool>,
+    draws: Cell<usize>,
+    culled: Cell<usize>,
+}
+
+impl<'a> Scope<'a> {
+    /// `base` with the GPU queries of this scope attached. Draws made with these
+    /// parameters are counted in the GPU time and the triangle count.
+    pub fn params<'s>(&'s self, base: &DrawParameters<'s>) -> DrawParameters<'s> {
+        self.used.set(true);
+        DrawParameters {
+            time_elapsed_query: self.time_elapsed.as_ref(),
+            primitives_generated_query: self.primitives.as_ref(),
+            ..base.clone()
+        }
+    }
+
+    /// Adds `count` to the number of draw calls of this scope.
+    pub fn count_draws(&self, count: usize) {
+        self.draws.set(self.draws.get() + count);
+    }
+
+    /// Adds `count` to the number of draw calls skipped by culling.
+    pub fn count_culled(&self, count: usize) {
+        self.culled.set(self.culled.get() + count);
+    }
+}
+
+impl<'a> Drop for Scope<'a> {
+    fn drop(&mut self) {
+        if !self.profiler.enabled {
+            return;
+        }
+        let end = time::precise_time_s();
+        // Queries which were never attached to a draw call have no results.
+        if !self.used.get() {
+            self.time_elapsed = None;
+            self.primitives = None;
+        }
+        self.profiler.pending.borrow_mut().push(Sample {
+            name: self.name,
+            start: self.start,
+            cpu: (end - self.start) * 1e3,
+            draws: self.draws.get(),
+            culled: self.culled.get(),
+            time_elapsed: self.time_elapsed.take(),
+            primitives: self.primitives.take(),
+        });
+    }
+}
+
+/// Averages over the last frames. Times are in milliseconds.
+#[derive(Debug, Clone)]
+pub struct Average {
+    pub cpu: f64,
+    pub gpu: Option<f64>,
+    pub draws: usize,
+    pub culled: usize,
+    pub triangles: Option<usize>,
+}
+
+struct Sample {
+    name: &'static str,
+    start: f64,
+    cpu: f64,
+    draws: usize,
+    culled: usize,
+    time_elapsed: Option<TimeElapsedQuery>,
+    primitives: Option<PrimitivesGeneratedQuery>,
+}
+
+impl Sample {
+    fn is_ready(&self) -> bool {
+        self.time_elapsed.as_ref().map_or(true, |q| q.is_ready()) &&
+        self.primitives.as_ref().map_or(true, |q| q.is_ready())
+    }
+}
+
+struct Measurement {
+    cpu: f64,
+    gpu: Option<f64>,
+    draws: usize,
+    culled: usize,
+    triangles: Option<usize>,
+}
+
+#[derive(Default)]
+struct Stats {
+    order: Vec<&'static str>,
+    scopes: HashMap<&'static str, Rolling>,
+}
+
+impl Stats {
+    fn add(&mut self, name: &'static str, measurement: Measurement) {
+        if !self.scopes.contains_key(name) {
+            self.order.push(name);
+        }
+        let rolling = self.scopes.entry(name).or_insert_with(|| Rolling(VecDeque::new()));
+        rolling.0.push_back(measurement);
+        while rolling.0.len() > WINDOW {
+            rolling.0.pop_front();
+        }
+    }
+}
+
+struct Rolling(VecDeque<Measurement>);
+
+impl Rolling {
+    fn average(&self) -> Average {
+        let n = self.0.len();
+        let gpu = self.0.iter().filter_map(|m| m.gpu).collect::<Vec<_>>();
+        let triangles = self.0.iter().filter_map(|m| m.triangles).collect::<Vec<_>>();
+        Average {
+            cpu: self.0.iter().fold(0.0, |acc, m| acc + m.cpu) / n as f64,
+            gpu: if gpu.is_empty() {
+                None
+            } else {
+                Some(gpu.iter().fold(0.0, |acc, &t| acc + t) / gpu.len() as f64)
+            },
+            draws: self.0.iter().fold(0, |acc, m| acc + m.draws) / n,
+            culled: self.0.iter().fold(0, |acc, m| acc + m.culled) / n,
+            triangles: if triangles.is_empty() {
+                None
+            } else {
+                Some(triangles.iter().fold(0, |acc, &t| acc + t) / triangles.len())
+            },
+        }
+    }
+}
+
+struct TraceEvent {
+    name: &'static str,
+    track: u32,
+    start_us: f64,
+    duration_us: f64,
+}
@@ -0,0 +1,242 @@
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter};
+use std::io::prelude::*;
+use std::path::{Path, PathBuf};
+use std::vec;
+
+use glium::glutin::{Event, ElementState, MouseButton, MouseScrollDelta, VirtualKeyCode};
+
+use {Result, Oops, oops};
+
+macro_rules! try_opt {
+    ($e:expr) => (match $e { Some(x) => x, None => return None })
+}
+
+/// Everything which influences the simulation in one iteration of the main
+/// loop: the events, the number of fixed updates, the interpolation factor and
+/// the number of camera updates while paused.
+#[derive(Debug, Clone)]
+pub struct InputFrame {
+    /// Seconds since the start of the recording, for information only.
+    pub timestamp: f64,
+    pub events: Vec<Event>,
+    pub updates: u32,
+    pub alpha: f32,
+    pub camera_updates: u32,
+}
+
+/// Writes `InputFrame`s to a text file as they happen.
+///
+/// Each frame is a `frame <timestamp> <updates> <alpha> <camera updates>` line,
+/// the last number may be left out when it is 0, followed by one
+/// line per event. Events which do not affect the painters (for example
+/// `Refresh`) are not recorded.
+pub struct InputRecorder {
+    path: PathBuf,
+    out: BufWriter<File>,
+}
+
+impl InputRecorder {
+    pub fn create<P: Into<PathBuf>>(path: P) -> Result<InputRecorder> {
+        let path = path.into();
+        info!("Recording input to {}", path.display());
+        let file = try!(File::create(&path)
+                            .map_err(|e| oops(format!("failed to create {}", path.display()), e)));
+        Ok(InputRecorder {
+            path: path,
+            out: BufWriter::new(file),
+        })
+    }
+
+    pub fn write(&mut self, frame: &InputFrame) -> Result<()> {
+        let mut text = format!("frame {:.6} {} {} {}\n",
+                               frame.timestamp,
+                               frame.updates,
+                               frame.alpha,
+                               frame.camera_updates);
+        for event in &frame.events {
+            if let Some(line) = format_event(event) {
+                text.push_str(&line);
+                text.push('\n');
+            }
+        }
+        self.out
+            .write_all(text.as_bytes())
+            .map_err(|e| oops(format!("failed to write {}", self.path.display()), e))
+    }
+}
+
+/// Plays back a file written by `InputRecorder`.
+pub struct InputReplay {
+    frames: vec::IntoIter<InputFrame>,
+}
+
+impl InputReplay {
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<InputReplay> {
+        let path = path.as_ref();
+        info!("Replaying input from {}", path.display());
+        let file = try!(File::open(path)
+                            .map_err(|e| oops(format!("failed to read {}", path.display()), e)));
+        let mut frames: Vec<InputFrame> = vec![];
+        for (i, line) in BufReader::new(file).lines().enumerate() {
+            let line = try!(line.map_err(|e| {
+                oops(format!("failed to read {}", path.display()), e)
+            }));
+            let invalid = || {
+                Oops::new(format!("{}:{}: invalid line {:?}", path.display(), i + 1, line))
+            };
+            let words = line.split_whitespace().collect::<Vec<_>>();
+            if words.is_empty() {
+                continue;
+            }
+            if words[0] == "frame" {
+                frames.push(try!(parse_frame(&words).ok_or_else(&invalid)));
+                continue;
+            }
+            let event = try!(parse_event(&words).ok_or_else(&invalid));
+            match frames.last_mut() {
+                Some(frame) => frame.events.push(event),
+                None => return Err(invalid()),
+            }
+        }
+        Ok(InputReplay { frames: frames.into_iter() })
+    }
+
+    pub fn next_frame(&mut self) -> Option<InputFrame> {
+        self.frames.next()
+    }
+}
+
+fn parse_frame(words: &[&str]) -> Option<InputFrame> {
+    if words.len() != 4 && words.len() != 5 {
+        return None;
+    }
+    Some(InputFrame {
+        timestamp: try_opt!(words[1].parse().ok()),
+        events: vec![],
+        updates: try_opt!(words[2].parse().ok()),
+        alpha: try_opt!(words[3].parse().ok()),
+        camera_updates: match words.get(4) {
+            Some(word) => try_opt!(word.parse().ok()),
+            None => 0,
+        },
+    })
+}
+
+fn format_event(event: &Event) -> Option<String> {
+    let line = match *event {
+        Event::KeyboardInput(state, scancode, code) => {
+            format!("key {} {} {}",
+                    format_state(state),
+                    scancode,
+                    code.and_then(key_name).unwrap_or("-"))
+        }
+        Event::MouseMoved((x, y)) => format!("mouse_moved {} {}", x, y),
+        Event::MouseInput(state, button) => {
+            let button = match button {
+                MouseButton::Left => "left".to_owned(),
+                MouseButton::Right => "right".to_owned(),
+                MouseButton::Middle => "middle".to_owned(),
+                MouseButton::Other(n) => n.to_string(),
+            };
+            format!("mouse_input {} {}", format_state(state), button)
+        }
+        Event::MouseWheel(delta, ..) => {
+            match delta {
+                MouseScrollDelta::LineDelta(x, y) => format!("mouse_wheel line {} {}", x, y),
+                MouseScrollDelta::PixelDelta(x, y) => format!("mouse_wheel pixel {} {}", x, y),
+            }
+        }
+        Event::Resized(width, height) => format!("resized {} {}", width, height),
+        Event::Focused(focused) => format!("focused {}", focused),
+        Event::Closed => "closed".to_owned(),
+        _ => return None,
+    };
+    Some(line)
+}
+
+fn parse_event(words: &[&str]) -> Option<Event> {
+    let event = match (words[0], words.len()) {
+        ("key", 4) => {
+            let code = if words[3] == "-" {
+                None
+            } else {
+                Some(try_opt!(key_code(words[3])))
+            };
+            Event::KeyboardInput(try_opt!(parse_state(words[1])),
+                                 try_opt!(words[2].parse().ok()),
+                                 code)
+        }
+        ("mouse_moved", 3) => {
+            Event::MouseMoved((try_opt!(words[1].parse().ok()), try_opt!(words[2].parse().ok())))
+        }
+        ("mouse_input", 3) => {
+            let button = match words[2] {
+                "left" => MouseButton::Left,
+                "right" => MouseButton::Right,
+                "middle" => MouseButton::Middle,
+                n => MouseButton::Other(try_opt!(n.parse().ok())),
+            };
+            Event::MouseInput(try_opt!(parse_state(words[1])), button)
+        }
+        ("mouse_wheel", 4) => {
+            let (x, y) = (try_opt!(words[2].parse().ok()), try_opt!(words[3].parse().ok()));
+            let delta = match words[1] {
+                "line" => MouseScrollDelta::LineDelta(x, y),
+                "pixel" => MouseScrollDelta::PixelDelta(x, y),
+                _ => return None,
+            };
+            Event::MouseWheel(delta)
+        }
+        ("resized", 3) => {
+            Event::Resized(try_opt!(words[1].parse().ok()), try_opt!(words[2].parse().ok()))
+        }
+        ("focused", 2) => Event::Focused(try_opt!(words[1].parse().ok())),
+        ("closed", 1) => Event::Closed,
+        _ => return None,
+    };
+    Some(event)
+}
+
+fn format_state(state: ElementState) -> &'static str {
+    match state {
+        ElementState::Pressed => "pressed",
+        ElementState::Released => "released",
+    }
+}
+
+fn parse_state(state: &str) -> Option<ElementState> {
+    match state {
+        "pressed" => Some(ElementState::Pressed),
+        "released" => Some(ElementState::Released),
+        _ => None,
+    }
+}
+
+macro_rules! keys {
+    ($($key:ident),*) => {
+        fn key_name(code: VirtualKeyCode) -> Option<&'static str> {
+            match code {
+                $(VirtualKeyCode::$key => Some(stringify!($key)),)*
+                _ => None,
+            }
+        }
+
+        pub fn key_code(name: &str) -> Option<VirtualKeyCode> {
+            match name {
+                $(stringify!($key) => Some(VirtualKeyCode::$key),)*
+                _ => None,
+            }
+        }
+    }
+}
+
+keys!(Key1, Key2, Key3, Key4, Key5, Key6, Key7, Key8, Key9, Key0,
+      A, B, C, D, E, F, G, H, I, J, K, L, M, N, O, P, Q, R, S, T, U, V, W, X, Y, Z,
+      Escape, F1, F2, F3, F4, F5, F6, F7, F8, F9, F10, F11, F12,
+      Insert, Home, Delete, End, PageDown, PageUp, Left, Up, Right, Down,
+      Back, Return, Space, Tab,
+      Numpad0, Numpad1, Numpad2, Numpad3, Numpad4, Numpad5, Numpad6, Numpad7, Numpad8, Numpad9,
+      Add, Subtract, Multiply, Divide, Comma, Period, Minus, Equals, Slash, Backslash,
+      Semicolon, Apostrophe, Grave, LBracket, RBracket,
+      LMenu, LControl, LShift, LWin, RMenu, RControl, RShift, RWin);
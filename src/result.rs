@@ -6,6 +6,7 @@ use glium::program::ProgramCreationError;
 use glium::vertex::BufferCreationError as VertexBufferCreationError;
 use glium::{SwapBuffersError, GliumCreationError, DrawError};
 use glium::texture::TextureCreationError;
+use glium::framebuffer::ValidationError;
 use glium::glutin::CreationError;
 use tobj::LoadError;
 use image::ImageError;
@@ -28,6 +29,14 @@ pub fn oops<E: Error + 'static, S: Into<String>>(message: S, err: E) -> Oops {
 }
 
 impl Oops {
+    pub fn new<S: Into<String>>(message: S) -> Oops {
+        Oops {
+            message: message.into(),
+            cause: None,
+            debug: None,
+        }
+    }
+
     pub fn from_debug<S: Into<String>, D: fmt::Debug>(message: S, err: D) -> Oops {
         Oops {
             message: message.into(),
@@ -125,4 +134,10 @@ impl From<TextureCreationError> for Oops{
     fn from (err: TextureCreationError) -> Oops{
         Oops::from_debug("failed to crate a texture", err)
     }
-}
\ No newline at end of file
+}
+
+impl From<ValidationError> for Oops {
+    fn from(err: ValidationError) -> Oops {
+        Oops::from_debug("failed to create a framebuffer", err)
+    }
+}
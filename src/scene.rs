@@ -0,0 +1,120 @@
+use glium::Frame;
+use glium::backend::glutin_backend::GlutinFacade;
+use glium::framebuffer::SimpleFrameBuffer;
+use glium::glutin::Event;
+
+use {Result, Oops, Painter, Api, Camera, Actions};
+use golden::Shot;
+
+
+/// A `Painter` behind a pointer, so that painters of different types can be
+/// registered together and switched at runtime. Implemented for every painter.
+pub trait Scene {
+    fn draw_frame(&self, api: &mut Api<Frame>) -> Result<()>;
+    fn draw_offscreen(&self, api: &mut Api<SimpleFrameBuffer>) -> Result<()>;
+    fn process_event(&mut self, event: Event);
+    fn update(&mut self, dt: f32, actions: &Actions);
+    fn update_camera(&mut self, dt: f32, actions: &Actions);
+    fn resize(&mut self, facade: &GlutinFacade, width: u32, height: u32) -> Result<()>;
+    fn clear_color(&self) -> (f32, f32, f32);
+    fn camera(&self) -> Option<&Camera>;
+    fn camera_mut(&mut self) -> Option<&mut Camera>;
+    fn golden_shots(&self) -> Vec<Shot>;
+}
+
+impl<P: Painter> Scene for P {
+    fn draw_frame(&self, api: &mut Api<Frame>) -> Result<()> {
+        self.draw(api)
+    }
+
+    fn draw_offscreen(&self, api: &mut Api<SimpleFrameBuffer>) -> Result<()> {
+        self.draw(api)
+    }
+
+    fn process_event(&mut self, event: Event) {
+        Painter::process_event(self, event)
+    }
+
+    fn update(&mut self, dt: f32, actions: &Actions) {
+        Painter::update(self, dt, actions)
+    }
+
+    fn update_camera(&mut self, dt: f32, actions: &Actions) {
+        Painter::update_camera(self, dt, actions)
+    }
+
+    fn resize(&mut self, facade: &GlutinFacade, width: u32, height: u32) -> Result<()> {
+        Painter::resize(self, facade, width, height)
+    }
+
+    fn clear_color(&self) -> (f32, f32, f32) {
+        P::clear_color()
+    }
+
+    fn camera(&self) -> Option<&Camera> {
+        Painter::camera(self)
+    }
+
+    fn camera_mut(&mut self) -> Option<&mut Camera> {
+        Painter::camera_mut(self)
+    }
+
+    fn golden_shots(&self) -> Vec<Shot> {
+        P::golden_shots()
+    }
+}
+
+type Constructor = fn(&GlutinFacade) -> Result<Box<Scene>>;
+
+/// Painters known to the launcher, by name. The order defines the number key
+/// which switches to a scene: `1` for the first one, `2` for the second and so on.
+pub struct Registry {
+    scenes: Vec<(&'static str, Constructor)>,
+}
+
+impl Registry {
+    pub fn new() -> Registry {
+        Registry { scenes: Vec::new() }
+    }
+
+    pub fn register<P: Painter + 'static>(mut self, name: &'static str) -> Registry {
+        self.scenes.push((name, construct::<P>));
+        self
+    }
+
+    pub fn len(&self) -> usize {
+        self.scenes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.scenes.is_empty()
+    }
+
+    pub fn names(&self) -> Vec<&'static str> {
+        self.scenes.iter().map(|&(name, _)| name).collect()
+    }
+
+    pub fn name(&self, index: usize) -> &'static str {
+        self.scenes[index].0
+    }
+
+    pub fn find(&self, name: &str) -> Result<usize> {
+        self.scenes
+            .iter()
+            .position(|&(n, _)| n == name)
+            .ok_or_else(|| {
+                Oops::new(format!("unknown scene {}, expected one of {}",
+                                  name,
+                                  self.names().join(", ")))
+            })
+    }
+
+    pub fn create(&self, index: usize, facade: &GlutinFacade) -> Result<Box<Scene>> {
+        info!("Loading scene {}", self.name(index));
+        (self.scenes[index].1)(facade)
+    }
+}
+
+fn construct<P: Painter + 'static>(facade: &GlutinFacade) -> Result<Box<Scene>> {
+    Ok(Box::new(try!(P::new(facade))))
+}
@@ -0,0 +1,61 @@
+use std::fs;
+use std::path::Path;
+
+use glium::texture::RawImage2d;
+use image;
+
+use {Result, oops};
+
+
+/// An RGBA8 image with rows stored top to bottom.
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<u8>,
+}
+
+impl Snapshot {
+    /// OpenGL stores rows bottom to top, so they are flipped here.
+    pub fn from_raw_image(raw: RawImage2d<'static, u8>) -> Snapshot {
+        let row = raw.width as usize * 4;
+        let pixels = raw.data
+                        .chunks(row)
+                        .rev()
+                        .flat_map(|r| r.iter().cloned())
+                        .collect::<Vec<_>>();
+        Snapshot {
+            width: raw.width,
+            height: raw.height,
+            pixels: pixels,
+        }
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Snapshot> {
+        let path = path.as_ref();
+        let im = try!(image::open(path)
+                          .map_err(|e| oops(format!("failed to read {}", path.display()), e)))
+                     .to_rgba();
+        let (width, height) = im.dimensions();
+        Ok(Snapshot {
+            width: width,
+            height: height,
+            pixels: im.into_raw(),
+        })
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        let path = path.as_ref();
+        if let Some(dir) = path.parent() {
+            try!(fs::create_dir_all(dir)
+                     .map_err(|e| oops(format!("failed to create {}", dir.display()), e)));
+        }
+        image::save_buffer(path, &self.pixels, self.width, self.height, image::RGBA(8))
+            .map_err(|e| oops(format!("failed to write {}", path.display()), e))
+    }
+
+    pub fn pixel(&self, x: u32, y: u32) -> [u8; 4] {
+        let i = 4 * (y * self.width + x) as usize;
+        [self.pixels[i], self.pixels[i + 1], self.pixels[i + 2], self.pixels[i + 3]]
+    }
+}